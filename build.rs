@@ -110,8 +110,11 @@ pub enum Dye {{
 }}
 
 impl Dye {{
+    /// The number of `Dye` variants, eighty-five.
+    pub const COUNT: usize = {count};
+
     /// Contains all eighty-five `Dye` variants.
-    pub const VALUES: [Dye; 85] = [
+    pub const VALUES: [Dye; Dye::COUNT] = [
         {values}
     ];
 
@@ -169,6 +172,7 @@ impl Dye {{
     }}
 }}"#,
                      variants = dyes.iter().zip(&variants).enumerate().map(|(i, (dye, variant))| format!("/// <div style=\"background-color: {:x}; width: 3em; height: 3em;\" aria-hidden=\"true\"></div>\n\t{variant} = {i}", dye.stain)).collect::<Vec<_>>().join(",\n\n\t"),
+                     count = variants.len(),
                      values = variants.iter().map(|dye| format!("Dye::{dye}")).collect::<Vec<_>>().join(",\n\t\t"),
 
                      categories = self.categories
@@ -197,8 +201,11 @@ pub enum Category {{
 }}
 
 impl Category {{
+    /// The number of `Category` variants, seven.
+    pub const COUNT: usize = {count};
+
     /// Contains all seven `Category` variants.
-    pub const VALUES: [Category; 7] = [
+    pub const VALUES: [Category; Category::COUNT] = [
         {values}
     ];
 
@@ -240,6 +247,7 @@ impl Category {{
     }}
 }}"#,
                      variants = categories.iter().enumerate().map(|(i, category)| format!("{category} = {i}")).collect::<Vec<_>>().join(",\n\t"),
+                     count = categories.len(),
                      values = categories.iter().map(|category| format!("Category::{category}")).collect::<Vec<_>>().join(",\n\t\t"),
 
                      dyes = self.categories
@@ -3,7 +3,7 @@ use std::io::{BufRead, Write};
 use std::process::exit;
 use std::str::FromStr;
 
-use chocodye::{Dye, FluentBundle, Lang, make_meal, make_menu, message, SnackList};
+use chocodye::{Dye, FluentBundle, Lang, make_meal, message, SnackList, write_menu};
 
 
 fn ask_dye(bundle: &FluentBundle, question: &'static str, default: Option<Dye>) -> io::Result<Dye> {
@@ -75,34 +75,7 @@ fn main() -> io::Result<()> {
     let meal = make_meal(starting_dye, final_dye);
     let snacks = SnackList::from(meal.as_slice());
 
-    println!("{}", message!(&bundle, "required-fruits"));
-    for (snack, count) in snacks.into_iter().filter(|(_, count)| *count > 0) {
-        println!("– {}", snack.quantified_name(&bundle, count as u32));
-    }
-    
-    if snacks.is_empty() {
-        println!("{}", message!(&bundle, "none"));
-    }
-    else {
-        println!();
-        
-        let menu = make_menu(starting_dye, snacks);
-        println!("{}", message!(&bundle, "feed-order"));
-        for (snack, count) in menu {
-            println!("– {}", snack.quantified_name(&bundle, count as u32));
-        }
-        
-        let ds = starting_dye.distance(final_dye);
-        let dd = Dye::DEFAULT_CHOCOBO_COLOR.distance(final_dye);
-        
-        if ds > dd {
-            let ss = snacks.sum();
-            let ds = make_meal(Dye::DEFAULT_CHOCOBO_COLOR, final_dye).len();
-            
-            println!();
-            println!("{}", message!(&bundle, "han-lemon-note", { "ratio" = format!("{:.1}", 100f32 * (1f32 - (ds as f32 / ss as f32))) }));
-        }
-    }
+    write_menu(&mut io::stdout().lock(), &bundle, starting_dye, final_dye, snacks)?;
 
     Ok(())
 }
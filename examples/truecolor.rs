@@ -4,7 +4,7 @@ use std::str::FromStr;
 
 use unic_langid::langid;
 
-use chocodye::{ansi_text, Category, Lang};
+use chocodye::{ansi_text, Category, Dye, Lang};
 
 #[cfg(unix)]
 fn get_term_width() -> Option<u16> {
@@ -131,8 +131,7 @@ fn  main() {
     println!();
 
     for category in Category::VALUES {
-        let mut dyes: Vec<_> = category.dyes().to_vec();
-        dyes.sort_unstable_by_key(|dye| 255 - dye.luma());
+        let dyes = Dye::sorted_by_luma().into_iter().filter(|dye| dye.category() == category);
 
         let category_full_name = category.full_name(&bundle);
         let colored_category_name = ansi_text(category.color(), category_full_name);
@@ -0,0 +1,72 @@
+use crate::lab::{from_linear, to_linear};
+use crate::Rgb;
+
+/// A kind of [color vision deficiency](https://en.wikipedia.org/wiki/Color_blindness).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CvdKind {
+    /// The absence of red cones.
+    Protanopia,
+
+    /// The absence of green cones.
+    Deuteranopia,
+
+    /// The absence of blue cones.
+    Tritanopia
+}
+
+impl CvdKind {
+    /// Contains all three `CvdKind` variants.
+    pub const VALUES: [CvdKind; 3] = [
+        CvdKind::Protanopia,
+        CvdKind::Deuteranopia,
+        CvdKind::Tritanopia
+    ];
+
+    /// Returns the matrix simulating `self` in linear RGB, from Machado et al. (2009) with a severity of `1.0`.
+    const fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            CvdKind::Protanopia => [
+                [ 0.152_286,  1.052_583, -0.204_868],
+                [ 0.114_503,  0.786_281,  0.099_216],
+                [-0.003_882, -0.048_116,  1.051_998]
+            ],
+            CvdKind::Deuteranopia => [
+                [ 0.367_322,  0.860_646, -0.227_968],
+                [ 0.280_085,  0.672_501,  0.047_413],
+                [-0.011_820,  0.042_940,  0.968_881]
+            ],
+            CvdKind::Tritanopia => [
+                [ 1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411,  0.930_809,  0.147_602],
+                [ 0.004_733,  0.691_367,  0.303_900]
+            ]
+        }
+    }
+}
+
+impl Rgb {
+    /// Returns how `self` would be perceived by someone having the specified color vision deficiency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{CvdKind, Rgb};
+    ///
+    /// let red = Rgb::RED.simulate_cvd(CvdKind::Protanopia);
+    /// let green = Rgb::GREEN.simulate_cvd(CvdKind::Protanopia);
+    ///
+    /// assert!(red.distance(green) < Rgb::RED.distance(Rgb::GREEN));
+    ///
+    /// // grays are unaffected
+    /// assert_eq!(Rgb::WHITE.simulate_cvd(CvdKind::Deuteranopia), Rgb::WHITE);
+    /// assert_eq!(Rgb::BLACK.simulate_cvd(CvdKind::Tritanopia), Rgb::BLACK);
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn simulate_cvd(self, kind: CvdKind) -> Rgb {
+        let linear = [to_linear(self.r), to_linear(self.g), to_linear(self.b)];
+        let [r, g, b] = kind.matrix().map(|row| row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+
+        Rgb::new(from_linear(r), from_linear(g), from_linear(b))
+    }
+}
@@ -4,7 +4,13 @@ use crate::{FluentBundle, message};
 #[cfg(all(feature = "fluent", feature = "truecolor"))]
 use crate::ansi_text;
 
-use crate::Rgb;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::{CvdKind, ParseHexError, Rgb};
 
 include!(concat!(env!("OUT_DIR"), "/dye.rs"));
 
@@ -15,6 +21,114 @@ impl Dye {
     /// The chocobos' default color.
     pub const DEFAULT_CHOCOBO_COLOR: Dye = Dye::DesertYellow;
 
+    /// Contains the colors of all eighty-five `Dye` variants, in the same order as [`Dye::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert!(Dye::VALUES.iter().zip(Dye::COLORS).all(|(dye, color)| dye.color() == color));
+    /// ```
+    pub const COLORS: [Rgb; Dye::COUNT] = {
+        let mut colors = [Rgb::BLACK; Dye::COUNT];
+
+        let mut i = 0;
+        while i < colors.len() {
+            colors[i] = Dye::VALUES[i].color();
+            i += 1;
+        }
+
+        colors
+    };
+
+    /// Contains all eighty-five `Dye` variants, from the brightest to the darkest according to [`Rgb::luma_sort_key`].
+    ///
+    /// Dyes having the same luma are in the same order as in [`Dye::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert_eq!(Dye::VALUES_BY_LUMA[0], Dye::LotusPink);
+    /// assert_eq!(Dye::VALUES_BY_LUMA[84], Dye::MidnightBlue);
+    /// ```
+    pub const VALUES_BY_LUMA: [Dye; Dye::COUNT] = {
+        let mut dyes = Dye::VALUES;
+
+        // stable insertion sort
+        let mut i = 1;
+        while i < dyes.len() {
+            let mut j = i;
+            while j > 0 && dyes[j - 1].color().luma_sort_key() > dyes[j].color().luma_sort_key() {
+                let tmp = dyes[j - 1];
+                dyes[j - 1] = dyes[j];
+                dyes[j] = tmp;
+                j -= 1;
+            }
+
+            i += 1;
+        }
+
+        dyes
+    };
+
+    /// Returns an iterator over all eighty-five `Dye` variants, in the same order as [`Dye::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert_eq!(Dye::iter().count(), 85);
+    /// ```
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Dye> {
+        Dye::VALUES.into_iter()
+    }
+
+    /// Returns all eighty-five `Dye` variants, from the brightest to the darkest. This is [`Dye::VALUES_BY_LUMA`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// let dyes = Dye::sorted_by_luma();
+    ///
+    /// assert_eq!(dyes[0], Dye::LotusPink);
+    /// assert!(dyes[0].luma() >= dyes[1].luma());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn sorted_by_luma() -> [Dye; Dye::COUNT] {
+        Dye::VALUES_BY_LUMA
+    }
+
+    /// Returns all eighty-five `Dye` variants, sorted by the [hue](Rgb::to_hsl) of their color, going from red through
+    /// yellow, green, blue and purple, and back to red.
+    ///
+    /// Grays have a hue of `0.0`, and are thus first along with the reds.
+    /// Dyes having the same hue are in the same order as in [`Dye::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// let dyes = Dye::sorted_by_hue();
+    ///
+    /// assert_eq!(dyes[0], Dye::SlateGrey);
+    /// assert_eq!(dyes[84], Dye::RolanberryRed);
+    /// ```
+    #[must_use]
+    pub fn sorted_by_hue() -> [Dye; Dye::COUNT] {
+        let mut dyes = Dye::VALUES;
+        dyes.sort_by(|a, b| a.color().to_hsl().0.total_cmp(&b.color().to_hsl().0));
+        dyes
+    }
+
     /// Computes the [squared Euclidian distance](https://en.wikipedia.org/wiki/Euclidean_distance#Squared_Euclidean_distance)
     /// between `self` and `other`. Does *not* take human perception into consideration. Useful for intermediate algorithms.
     ///
@@ -40,8 +154,8 @@ impl Dye {
     /// ```
     /// use chocodye::Dye;
     ///
-    /// assert_eq!(Dye::SnowWhite.luma(), 222);
-    /// assert_eq!(Dye::SootBlack.luma(), 40);
+    /// assert_eq!(Dye::SnowWhite.luma(), 223);
+    /// assert_eq!(Dye::SootBlack.luma(), 41);
     ///
     /// assert!(Dye::HunterGreen.luma() > Dye::WineRed.luma()); // Humans are more sensitive to green.
     /// ```
@@ -51,6 +165,117 @@ impl Dye {
         self.color().luma()
     }
 
+    /// Returns the components of `self`'s color followed by `alpha`, the byte layout of RGBA8 image buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert_eq!(Dye::AppleGreen.to_rgba_bytes(128), [155, 179, 99, 128]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_rgba_bytes(self, alpha: u8) -> [u8; 4] {
+        self.color().to_rgba_bytes(alpha)
+    }
+
+    /// Returns the dye that is the next step brighter than `self`, or `None` if `self` is already the brightest dye.
+    ///
+    /// The returned dye has the smallest [luma](Dye::luma) that is greater than `self`'s;
+    /// dyes having the same luma are discriminated by their [distance](Dye::distance) to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert!(Dye::SootBlack.lighter().unwrap().luma() > Dye::SootBlack.luma());
+    /// assert_eq!(Dye::SnowWhite.lighter().map(Dye::darker), Some(Some(Dye::SnowWhite)));
+    /// ```
+    #[must_use]
+    pub fn lighter(self) -> Option<Dye> {
+        Dye::VALUES.into_iter()
+            .filter(|dye| dye.luma() > self.luma())
+            .min_by_key(|dye| (dye.luma(), dye.distance(self)))
+    }
+
+    /// Returns the dye that is the next step darker than `self`, or `None` if `self` is already the darkest dye.
+    ///
+    /// The returned dye has the largest [luma](Dye::luma) that is smaller than `self`'s;
+    /// dyes having the same luma are discriminated by their [distance](Dye::distance) to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert!(Dye::SnowWhite.darker().unwrap().luma() < Dye::SnowWhite.luma());
+    /// assert_eq!(Dye::MidnightBlue.darker(), None); // the darkest dye
+    /// ```
+    #[must_use]
+    pub fn darker(self) -> Option<Dye> {
+        Dye::VALUES.into_iter()
+            .filter(|dye| dye.luma() < self.luma())
+            .min_by_key(|dye| (u8::MAX - dye.luma(), dye.distance(self)))
+    }
+
+    /// Returns all the dyes of `category`, or of any category if `None`, whose [luma](Dye::luma) is within `luma`,
+    /// in the same order as [`Dye::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Dye};
+    ///
+    /// let dark_blues = Dye::query(Some(Category::Blue), 0..=60);
+    ///
+    /// assert!(dark_blues.contains(&Dye::InkBlue));
+    /// assert!(dark_blues.iter().all(|dye| dye.category() == Category::Blue && dye.luma() <= 60));
+    ///
+    /// assert_eq!(Dye::query(None, 0..=u8::MAX), Dye::VALUES);
+    /// assert_eq!(Dye::query(Some(Category::White), 0..=10), []);
+    /// ```
+    #[must_use]
+    pub fn query(category: Option<Category>, luma: RangeInclusive<u8>) -> Vec<Dye> {
+        category.map_or(Dye::VALUES.as_slice(), Category::dyes).iter()
+            .copied()
+            .filter(|dye| luma.contains(&dye.luma()))
+            .collect()
+    }
+
+    /// Returns `steps` evenly spaced colors going from `self`'s color to `other`'s, both included.
+    ///
+    /// The intermediate colors are computed with [`Rgb::lerp`], and usually aren't dye colors;
+    /// [`Dye::try_from`] snaps them back to the nearest dyes. If `steps` is smaller than `2`, only the first endpoints fit:
+    /// `1` returns `self`'s color only, and `0` returns an empty vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// let gradient = Dye::SootBlack.gradient(Dye::SnowWhite, 5);
+    ///
+    /// assert_eq!(gradient.len(), 5);
+    /// assert_eq!(gradient.first(), Some(&Dye::SootBlack.color()));
+    /// assert_eq!(gradient.last(), Some(&Dye::SnowWhite.color()));
+    ///
+    /// assert_eq!(Dye::SootBlack.gradient(Dye::SnowWhite, 1), [Dye::SootBlack.color()]);
+    /// assert_eq!(Dye::SootBlack.gradient(Dye::SnowWhite, 0), []);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn gradient(self, other: Dye, steps: usize) -> Vec<Rgb> {
+        let (from, to) = (self.color(), other.color());
+
+        match steps {
+            0 => Vec::new(),
+            1 => vec![from],
+            _ => (0..steps).map(|i| from.lerp(to, i as f32 / (steps - 1) as f32)).collect()
+        }
+    }
+
     /// Returns the localized name of `self`'s color.
     ///
     /// # Examples
@@ -77,10 +302,12 @@ impl Dye {
         ansi_text(self.color(), self.color_name(bundle))
     }
 
-    /// Parses a localized color name into its original [`Dye`].
+    /// Parses a localized color name into its original [`Dye`], leniently.
     ///
-    /// Eszetts must have been replaced by "ss". The current implementation is case-insensitive,
-    /// but no diacritic-insensitve. Future implementations may be more permissive.
+    /// The comparison ignores case, diacritics, apostrophes and whitespace, and `ß` matches `ss`,
+    /// which makes it suitable for search boxes. Diacritics are stripped after a canonical decomposition (NFD),
+    /// but only the Latin combining marks are removed, so that the Japanese voiced marks are still significant.
+    /// See [`Dye::from_str_exact`] for a stricter comparison.
     ///
     /// # Examples
     ///
@@ -90,104 +317,712 @@ impl Dye {
     /// let de = Lang::German.into_bundle();
     ///
     /// assert_eq!(Dye::from_str(&de, "Ul'dahbraun"), Some(Dye::UlBrown));    // exact match
-    /// assert_eq!(Dye::from_str(&de, "Ul dahbraun"), None);                  // missing apostrophe
+    /// assert_eq!(Dye::from_str(&de, "Ul dahbraun"), Some(Dye::UlBrown));    // apostrophes and spaces are ignored
+    /// assert_eq!(Dye::from_str(&de, "uldahbraun"), Some(Dye::UlBrown));
     /// assert_eq!(Dye::from_str(&de, "tÜrkIS"), Some(Dye::TurquoiseGreen));  // case is ignored
-    /// assert_eq!(Dye::from_str(&de, "Turkis"), None);                       // missing umlaut
-    /// assert_eq!(Dye::from_str(&de, "Russschwarz"), Some(Dye::SootBlack));  // `ß` was replaced by `ss`
-    /// assert_eq!(Dye::from_str(&de, "Rußschwarz"), None);                   // `ß` wasn't replaced by `ss`
+    /// assert_eq!(Dye::from_str(&de, "turkis"), Some(Dye::TurquoiseGreen));  // diacritics are ignored
+    /// assert_eq!(Dye::from_str(&de, "Russschwarz"), Some(Dye::SootBlack));  // `ß` matches `ss`
+    /// assert_eq!(Dye::from_str(&de, "Rußschwarz"), Some(Dye::SootBlack));
+    /// assert_eq!(Dye::from_str(&de, "Rostblau"), None);
     /// ```
     #[cfg(feature = "fluent")]
     #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
     #[must_use]
     pub fn from_str(bundle: &FluentBundle, color_name: &str) -> Option<Dye> {
+        let s = fold_name(color_name);
+
+        Dye::VALUES.into_iter().find(|dye| fold_name(dye.color_name(bundle)) == s)
+    }
+
+    /// Parses a localized color name into its original [`Dye`].
+    ///
+    /// Eszetts must have been replaced by "ss". The comparison is case-insensitive,
+    /// but not diacritic-insensitive. See [`Dye::from_str`] for a more permissive comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Lang};
+    ///
+    /// let de = Lang::German.into_bundle();
+    ///
+    /// assert_eq!(Dye::from_str_exact(&de, "Ul'dahbraun"), Some(Dye::UlBrown));    // exact match
+    /// assert_eq!(Dye::from_str_exact(&de, "Ul dahbraun"), None);                  // missing apostrophe
+    /// assert_eq!(Dye::from_str_exact(&de, "tÜrkIS"), Some(Dye::TurquoiseGreen));  // case is ignored
+    /// assert_eq!(Dye::from_str_exact(&de, "Turkis"), None);                       // missing umlaut
+    /// assert_eq!(Dye::from_str_exact(&de, "Russschwarz"), Some(Dye::SootBlack));  // `ß` was replaced by `ss`
+    /// assert_eq!(Dye::from_str_exact(&de, "Rußschwarz"), None);                   // `ß` wasn't replaced by `ss`
+    /// ```
+    #[cfg(feature = "fluent")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+    #[must_use]
+    pub fn from_str_exact(bundle: &FluentBundle, color_name: &str) -> Option<Dye> {
         let s = color_name.to_lowercase();
 
-        Dye::VALUES.into_iter().find(|dye| dye.color_name(bundle).replace('ß', "ss").replace('’', "'").to_lowercase() == s)
+        Dye::VALUES.into_iter().find(|dye| normalize_name(dye.color_name(bundle)) == s)
     }
-}
 
-impl Category {
-    /// Returns the localized name of `self`.
+    /// Returns all the dyes whose localized color name starts with `prefix`, sorted by name.
+    ///
+    /// The comparison is case-insensitive, `ß` matches `ss` and `’` matches `'`, both in the prefix and in the names;
+    /// an empty prefix returns all the dyes.
     ///
     /// # Examples
     ///
     /// ```
-    /// use chocodye::{Category, Lang};
+    /// use chocodye::{Dye, Lang};
     ///
-    /// assert_eq!(Category::Purple.full_name(&Lang::French.into_bundle()), "Teintures violettes");
+    /// let en = Lang::English.into_bundle();
+    ///
+    /// assert_eq!(Dye::search_prefix(&en, "sky"), [Dye::SkyBlue]);
+    /// assert_eq!(Dye::search_prefix(&en, "Snow"), [Dye::SnowWhite]);
+    /// assert_eq!(Dye::search_prefix(&en, "Sh"), [Dye::ShadowBlue, Dye::ShaleBrown]);
+    /// assert_eq!(Dye::search_prefix(&en, "s").len(), 11);
+    /// assert_eq!(Dye::search_prefix(&en, "Skye"), []);
+    ///
+    /// let de = Lang::German.into_bundle();
+    ///
+    /// assert_eq!(Dye::search_prefix(&de, "Ruß"), Dye::search_prefix(&de, "Russ"));
+    /// assert!(Dye::search_prefix(&de, "Ruß").contains(&Dye::SootBlack));
     /// ```
     #[cfg(feature = "fluent")]
     #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
     #[must_use]
-    pub fn full_name(self, bundle: &FluentBundle) -> &str {
-        message!(bundle, self.short_name())
+    pub fn search_prefix(bundle: &FluentBundle, prefix: &str) -> Vec<Dye> {
+        let prefix = normalize_name(prefix);
+
+        let mut dyes: Vec<(Dye, String)> = Dye::VALUES.into_iter()
+            .map(|dye| (dye, normalize_name(dye.color_name(bundle))))
+            .filter(|(_, name)| name.starts_with(&prefix))
+            .collect();
+
+        dyes.sort_by(|(_, a), (_, b)| a.cmp(b));
+        dyes.into_iter().map(|(dye, _)| dye).collect()
     }
-    
-    /// Returns the localized name of `self` with [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit) for display in `stdout`.
+
+    /// Parses a hex color into its closest [`Dye`].
     ///
-    /// For more documentation, check the [`ansi_text`] function. This function is also used in the `truecolor` example.
-    #[cfg(all(feature = "fluent", feature = "truecolor"))]
-    #[cfg_attr(docsrs, doc(cfg(all(feature = "fluent", feature = "truecolor"))))]
+    /// The returned boolean is `true` for an exact match, or `false` if the dye is only an approximation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert_eq!(Dye::parse_nearest("#9bb363"), Ok((Dye::AppleGreen, true)));
+    /// assert_eq!(Dye::parse_nearest("#9bb362"), Ok((Dye::AppleGreen, false)));
+    /// assert!(Dye::parse_nearest("9bb363").is_err());
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    pub fn parse_nearest(s: &str) -> Result<(Dye, bool), ParseHexError> {
+        Ok(match Dye::try_from(Rgb::from_hex(s)?) {
+            Ok(dye) => (dye, true),
+            Err(dye) => (dye, false)
+        })
+    }
+
+    /// Parses a color in the `#rrggbb` or `#rgb` format, then converts it to a dye like [`Dye::try_from`].
+    ///
+    /// The result is nested: the outer `Result` is `Err(_)` if `s` is not a valid color, and the inner one is
+    /// `Ok(_)` if the color is exactly the one of a dye, or `Err(_)` with the closest dye otherwise.
+    /// See [`Dye::parse_nearest`] for a flattened result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, ParseHexError};
+    ///
+    /// assert_eq!(Dye::from_hex("#9bb363"), Ok(Ok(Dye::AppleGreen)));          // an in-game dye
+    /// assert_eq!(Dye::from_hex("#9bb362"), Ok(Err(Dye::AppleGreen)));         // close to an in-game dye
+    /// assert_eq!(Dye::from_hex("9bb3633"), Err(ParseHexError::MissingHash));  // not a color
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    pub fn from_hex(s: &str) -> Result<Result<Dye, Dye>, ParseHexError> {
+        Rgb::from_hex(s).map(Dye::try_from)
+    }
+
+    /// Returns the closest [`Dye`] to `color` if their [squared distance](Rgb::distance) is at most `tolerance`, or `None` otherwise.
+    ///
+    /// A tolerance of `0` only accepts exact matches, like [`Dye::try_from`]. Unlike `Dye::try_from`, which always returns
+    /// the closest dye however far it is, this can tell whether a color is approximately a dye, e.g. to ignore the
+    /// background pixels of a screenshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// assert_eq!(Dye::from_color_within(Rgb::new(155, 179, 99), 0), Some(Dye::AppleGreen));
+    /// assert_eq!(Dye::from_color_within(Rgb::new(155, 179, 101), 4), Some(Dye::AppleGreen));
+    /// assert_eq!(Dye::from_color_within(Rgb::new(155, 179, 101), 3), None);
+    /// ```
     #[must_use]
-    pub fn ansi_full_name(self, bundle: &FluentBundle) -> String {
-        ansi_text(self.color(), self.full_name(bundle))
+    pub fn from_color_within(color: Rgb, tolerance: u32) -> Option<Dye> {
+        let dye = Dye::try_from(color).unwrap_or_else(|dye| dye);
+
+        (dye.color().distance(color) <= tolerance).then_some(dye)
     }
-}
 
-impl From<Dye> for Rgb {
-    /// Converts a dye into its color.
+    /// Returns the closest [`Dye`] to `color` if their [squared distance](Rgb::distance) is at most `max_distance`.
+    ///
+    /// This is an alias of [`Dye::from_color_within`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// assert_eq!(Dye::closest_within(Rgb::new(155, 179, 101), 4), Some(Dye::AppleGreen));
+    /// assert_eq!(Dye::closest_within(Rgb::new(155, 179, 101), 3), None);
+    /// ```
+    #[must_use]
     #[inline]
-    fn from(dye: Dye) -> Rgb {
-        dye.color()
+    pub fn closest_within(color: Rgb, max_distance: u32) -> Option<Dye> {
+        Dye::from_color_within(color, max_distance)
     }
-}
 
-impl TryFrom<Rgb> for Dye {
-    /// The closest match if there is no exact match.
-    type Error = Dye;
+    /// Returns the [`Dye`] perceived as the closest to `color`, using the [CIEDE2000](Rgb::delta_e) color difference.
+    ///
+    /// This is slower than `Dye::try_from(color)`, which uses the [squared Euclidean distance](Rgb::distance),
+    /// but better matches what a human would pick, e.g. in a color picker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// assert_eq!(Dye::nearest_perceptual(Dye::AppleGreen.color()), Dye::AppleGreen);
+    /// ```
+    #[must_use]
+    pub fn nearest_perceptual(color: Rgb) -> Dye {
+        Dye::VALUES.into_iter()
+            .min_by(|a, b| a.color().delta_e(color).total_cmp(&b.color().delta_e(color)))
+            .unwrap_or(Dye::VALUES[0])
+    }
 
-    /// Converts a color to a dye, returning `Ok(_)` for an exact match, or `Err(_)` for the closest match.
+    /// Returns the `k` closest dyes to `color` with their [squared distances](Rgb::distance), from the closest to the furthest.
+    ///
+    /// Ties are broken like [`Dye::try_from`], in favor of the first dye in [`Dye::VALUES`];
+    /// the first entry is therefore always the closest dye. At most eighty-five dyes are returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use chocodye::{Dye, Rgb};
-    /// # use std::convert::identity;
     ///
-    /// assert_eq!(Dye::AppleGreen.color(), Rgb::new(155, 179, 99));
-    /// assert_eq!(Dye::try_from(Rgb::new(155, 179, 99)), Ok(Dye::AppleGreen));
-    /// assert_eq!(Dye::try_from(Rgb::new(155, 179, 98)), Err(Dye::AppleGreen));
+    /// let nearest = Dye::top_k_nearest(Rgb::new(155, 179, 101), 3);
     ///
-    /// // use `std::convert::identity` if you don't care about exact matches
-    /// assert_eq!(Dye::try_from(Rgb::WHITE).unwrap_or_else(identity), Dye::LotusPink);
-    /// assert_eq!(Dye::try_from(Rgb::BLACK).unwrap_or_else(identity), Dye::InkBlue);
+    /// assert_eq!(nearest.len(), 3);
+    /// assert_eq!(nearest[0], (Dye::AppleGreen, 4));
+    /// assert!(nearest[1].1 <= nearest[2].1);
     /// ```
-    fn try_from(value: Rgb) -> Result<Dye, Self::Error> {
-        let dye = Dye::VALUES.into_iter().min_by_key(|d| d.color().distance(value)).unwrap();
-        
-        if dye.color() == value {
-            Ok(dye)
-        }
-        else {
-            Err(dye)
-        }
+    #[must_use]
+    pub fn top_k_nearest(color: Rgb, k: usize) -> Vec<(Dye, u32)> {
+        let mut dyes: Vec<(Dye, u32)> = Dye::VALUES.into_iter().map(|dye| (dye, dye.color().distance(color))).collect();
+
+        dyes.sort_by_key(|&(_, distance)| distance);
+        dyes.truncate(k);
+        dyes
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// Returns all the pairs of dyes whose colors are perceived as closer than `threshold`,
+    /// optionally as seen by someone having a color vision deficiency.
+    ///
+    /// The distance is the [CIEDE2000](Rgb::delta_e) color difference, where `1.0` is roughly the smallest noticeable
+    /// difference. Each pair is only returned once, in [`Dye::VALUES`] order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{CvdKind, Dye};
+    ///
+    /// assert!(Dye::confusable_pairs(5.0, None).len() < Dye::confusable_pairs(5.0, Some(CvdKind::Deuteranopia)).len());
+    /// ```
+    #[must_use]
+    pub fn confusable_pairs(threshold: f32, cvd: Option<CvdKind>) -> Vec<(Dye, Dye)> {
+        let colors = Dye::COLORS.map(|color| cvd.map_or(color, |kind| color.simulate_cvd(kind)));
 
-    #[test]
-    fn dyes_in_self_category() {
-        assert_eq!(Dye::VALUES.len(), Category::VALUES.iter().map(|category| category.dyes().len()).sum());
+        let mut pairs = Vec::new();
 
-        for category in Category::VALUES {
-            assert!(category.dyes().iter().all(|dye| dye.category() == category));
+        for (i, a) in colors.into_iter().enumerate() {
+            for (j, b) in colors.into_iter().enumerate().skip(i + 1) {
+                if a.delta_e(b) < threshold {
+                    pairs.push((Dye::VALUES[i], Dye::VALUES[j]));
+                }
+            }
         }
+
+        pairs
     }
+}
 
-    #[test]
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+impl clap::ValueEnum for Dye {
+    /// Returns all eighty-five dyes, [`Dye::VALUES`].
+    #[inline]
+    fn value_variants<'a>() -> &'a [Dye] {
+        &Dye::VALUES
+    }
+
+    /// Returns the [short name](Dye::short_name) of `self`, e.g. `snow-white`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    /// use clap::ValueEnum;
+    ///
+    /// assert_eq!(<Dye as ValueEnum>::from_str("snow-white", false), Ok(Dye::SnowWhite));
+    /// ```
+    #[inline]
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.short_name()))
+    }
+}
+
+#[cfg(feature = "simd")]
+impl Dye {
+    /// Returns the closest [`Dye`] to `color`, computing all eighty-five distances with SIMD instructions.
+    ///
+    /// Always returns the same dye as `Dye::try_from(color).unwrap_or_else(identity)`, but is faster when called repeatedly,
+    /// e.g. for every pixel of an image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// assert_eq!(Dye::nearest_simd(Rgb::new(155, 179, 98)), Dye::AppleGreen);
+    /// assert_eq!(Dye::nearest_simd(Rgb::WHITE), Dye::LotusPink);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "simd")))]
+    #[must_use]
+    pub fn nearest_simd(color: Rgb) -> Dye {
+        use wide::{CmpLt, i32x8};
+
+        const LANES: usize = 8;
+        const CHUNKS: usize = (Dye::COLORS.len() + LANES - 1) / LANES;
+
+        /// A component far enough from `0..=255` so that padding lanes are never the closest.
+        const PAD: i32 = 1 << 12;
+
+        /// `Dye::COLORS` as a structure of arrays, chunked into SIMD vectors.
+        const SOA: [[i32x8; 3]; CHUNKS] = {
+            let mut soa = [[i32x8::new([PAD; LANES]); 3]; CHUNKS];
+
+            let mut i = 0;
+            while i < CHUNKS {
+                let mut r = [PAD; LANES];
+                let mut g = [PAD; LANES];
+                let mut b = [PAD; LANES];
+
+                let mut j = 0;
+                while j < LANES && i * LANES + j < Dye::COLORS.len() {
+                    let color = Dye::COLORS[i * LANES + j];
+
+                    r[j] = color.r as i32;
+                    g[j] = color.g as i32;
+                    b[j] = color.b as i32;
+                    j += 1;
+                }
+
+                soa[i] = [i32x8::new(r), i32x8::new(g), i32x8::new(b)];
+                i += 1;
+            }
+
+            soa
+        };
+
+        let r = i32x8::splat(color.r.into());
+        let g = i32x8::splat(color.g.into());
+        let b = i32x8::splat(color.b.into());
+
+        let mut min_distance = i32x8::splat(i32::MAX);
+        let mut min_index = i32x8::splat(0);
+
+        // the comparison is strict, so each lane keeps its first minimum
+        for (base, [cr, cg, cb]) in (0..).step_by(LANES).zip(SOA) {
+            let (dr, dg, db) = (cr - r, cg - g, cb - b);
+            let distance = dr * dr + dg * dg + db * db;
+
+            let closer = distance.cmp_lt(min_distance);
+            min_distance = closer.blend(distance, min_distance);
+            min_index = closer.blend(i32x8::splat(base) + i32x8::new([0, 1, 2, 3, 4, 5, 6, 7]), min_index);
+        }
+
+        let (_, index) = min_distance.to_array().into_iter().zip(min_index.to_array()).min().unwrap();
+        Dye::VALUES[index as usize]
+    }
+}
+
+impl Category {
+    /// Returns an iterator over all seven `Category` variants, in the same order as [`Category::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Category;
+    ///
+    /// assert_eq!(Category::iter().count(), 7);
+    /// ```
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Category> {
+        Category::VALUES.into_iter()
+    }
+
+    /// Returns the category whose [representative color](Category::color) is the closest to `color`,
+    /// along with a confidence between `0.0` and `1.0`.
+    ///
+    /// The confidence is `1.0` if `color` is exactly the representative color, and `0.0` if `color` is
+    /// as close to the second-best category as it is to the best one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Rgb};
+    ///
+    /// assert_eq!(Category::classify(Category::Red.color()), (Category::Red, 1.0));
+    ///
+    /// let (category, confidence) = Category::classify(Rgb::new(200, 60, 60));
+    /// assert_eq!(category, Category::Red);
+    /// assert!(confidence > 0.5);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn classify(color: Rgb) -> (Category, f32) {
+        let mut distances = Category::VALUES.map(|category| (category.color().distance(color), category));
+        distances.sort_by_key(|(distance, _)| *distance);
+
+        let (best, category) = distances[0];
+        let (second, _) = distances[1];
+
+        let confidence = if second == 0 {
+            0.0
+        }
+        else {
+            1.0 - (f64::from(best) / f64::from(second)).sqrt() as f32
+        };
+
+        (category, confidence)
+    }
+
+    /// Returns the category whose [representative color](Category::color) is the closest to `color`,
+    /// by [squared distance](Rgb::distance).
+    ///
+    /// This is the category returned by [`Category::classify`], without the confidence. As the representative colors
+    /// are hand-picked and saturated, many dyes are closer to the representative color of another category,
+    /// e.g. most grays and greens are closer to brown; see [`Category::nearest_by_dyes`] for the category
+    /// owning the nearest dye instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Dye, Rgb};
+    ///
+    /// assert_eq!(Category::nearest(Rgb::new(200, 60, 60)), Category::Red);
+    ///
+    /// assert_eq!(Category::nearest(Dye::SootBlack.color()), Category::Brown);
+    /// assert_eq!(Category::nearest_by_dyes(Dye::SootBlack.color()), Category::White);
+    /// ```
+    #[must_use]
+    pub fn nearest(color: Rgb) -> Category {
+        Category::classify(color).0
+    }
+
+    /// Returns the category of the [`Dye`] nearest to `color`, by [squared distance](Rgb::distance).
+    ///
+    /// Unlike [`Category::nearest`], which only compares the seven representative colors, this follows the actual
+    /// dyes, and thus always agrees with [`Dye::category`] for the color of a dye; both may disagree, e.g. for dark colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Dye, Rgb};
+    ///
+    /// assert_eq!(Category::nearest_by_dyes(Rgb::new(40, 40, 240)), Category::Blue);
+    /// assert_eq!(Category::nearest_by_dyes(Dye::MidnightBlue.color()), Category::Blue);
+    /// ```
+    #[must_use]
+    pub fn nearest_by_dyes(color: Rgb) -> Category {
+        Dye::try_from(color).unwrap_or_else(|dye| dye).category()
+    }
+
+    /// Returns the average color of the dyes of `self`, rounded to the nearest integer.
+    ///
+    /// Unlike the hand-picked [`Category::color`], this follows the actual dyes. Averaging desaturates the colors, so the
+    /// centroid is usually much grayer than the representative color, and is not necessarily the closest to its own dyes:
+    /// the dye closest to the red, yellow and purple centroids is respectively a brown, a brown and a red dye.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Rgb};
+    ///
+    /// assert_eq!(Category::Red.centroid(), Rgb::new(146, 78, 69));
+    /// ```
+    #[must_use]
+    pub fn centroid(self) -> Rgb {
+        let colors: Vec<Rgb> = self.dyes().iter().map(|dye| dye.color()).collect();
+
+        Rgb::mix(&colors).expect("categories are never empty")
+    }
+
+    /// Returns the localized name of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Lang};
+    ///
+    /// assert_eq!(Category::Purple.full_name(&Lang::French.into_bundle()), "Teintures violettes");
+    /// ```
+    #[cfg(feature = "fluent")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+    #[must_use]
+    pub fn full_name(self, bundle: &FluentBundle) -> &str {
+        message!(bundle, self.short_name())
+    }
+
+    /// Returns the dyes of `self` along with their localized names, in the same order as [`Category::dyes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Category, Dye, Lang};
+    ///
+    /// let bundle = Lang::English.into_bundle();
+    /// let dyes = Category::Blue.localized_dyes(&bundle);
+    ///
+    /// assert!(dyes.contains(&(Dye::InkBlue, "Ink Blue")));
+    /// ```
+    #[cfg(feature = "fluent")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+    #[must_use]
+    pub fn localized_dyes(self, bundle: &FluentBundle) -> Vec<(Dye, &str)> {
+        self.dyes().iter().map(|&dye| (dye, dye.color_name(bundle))).collect()
+    }
+    
+    /// Returns the localized name of `self` with [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit) for display in `stdout`.
+    ///
+    /// For more documentation, check the [`ansi_text`] function. This function is also used in the `truecolor` example.
+    #[cfg(all(feature = "fluent", feature = "truecolor"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "fluent", feature = "truecolor"))))]
+    #[must_use]
+    pub fn ansi_full_name(self, bundle: &FluentBundle) -> String {
+        ansi_text(self.color(), self.full_name(bundle))
+    }
+}
+
+impl Rgb {
+    /// Tints `self` with `dye`, as if the dye was layered over `self` with the specified `strength`.
+    ///
+    /// This is a [linear interpolation](Rgb::lerp) towards the dye's color; a strength of `0.0` leaves `self` unchanged,
+    /// and a strength of `1.0` fully covers it. The strength is clamped between these two values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    ///
+    /// assert_eq!(Rgb::WHITE.stain(Dye::SootBlack, 0.0), Rgb::WHITE);
+    /// assert_eq!(Rgb::WHITE.stain(Dye::SootBlack, 1.0), Dye::SootBlack.color());
+    /// assert_eq!(Rgb::BLACK.stain(Dye::SnowWhite, 0.5), Rgb::new(114, 112, 104));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn stain(self, dye: Dye, strength: f32) -> Rgb {
+        self.lerp(dye.color(), strength)
+    }
+}
+
+impl From<Dye> for Rgb {
+    /// Converts a dye into its color.
+    #[inline]
+    fn from(dye: Dye) -> Rgb {
+        dye.color()
+    }
+}
+
+impl PartialOrd for Dye {
+    #[inline]
+    fn partial_cmp(&self, other: &Dye) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Dye {
+    /// Compares two dyes by brightness, **not** by declaration order: the darker dye is the smaller one.
+    ///
+    /// Dyes are ordered by their [luma](Dye::luma); dyes having the same luma are ordered as in [`Dye::VALUES`],
+    /// so that the ordering is total and deterministic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert!(Dye::SnowWhite > Dye::SootBlack); // although `SnowWhite` is declared first
+    ///
+    /// let mut dyes = Dye::VALUES;
+    /// dyes.sort();
+    ///
+    /// assert_eq!(dyes.first(), Some(&Dye::MidnightBlue)); // the darkest dye
+    /// ```
+    #[inline]
+    fn cmp(&self, other: &Dye) -> Ordering {
+        (self.luma(), *self as u8).cmp(&(other.luma(), *other as u8))
+    }
+}
+
+impl TryFrom<u8> for Dye {
+    /// The out-of-range discriminant.
+    type Error = u8;
+
+    /// Returns the dye whose discriminant is `value`, the inverse of `dye as u8`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    ///
+    /// assert_eq!(Dye::try_from(Dye::InkBlue as u8), Ok(Dye::InkBlue));
+    /// assert_eq!(Dye::try_from(85), Err(85));
+    /// ```
+    #[inline]
+    fn try_from(value: u8) -> Result<Dye, u8> {
+        Dye::VALUES.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
+impl TryFrom<u8> for Category {
+    /// The out-of-range discriminant.
+    type Error = u8;
+
+    /// Returns the category whose discriminant is `value`, the inverse of `category as u8`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Category;
+    ///
+    /// assert_eq!(Category::try_from(Category::Blue as u8), Ok(Category::Blue));
+    /// assert_eq!(Category::try_from(7), Err(7));
+    /// ```
+    #[inline]
+    fn try_from(value: u8) -> Result<Category, u8> {
+        Category::VALUES.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
+impl TryFrom<Rgb> for Dye {
+    /// The closest match if there is no exact match.
+    type Error = Dye;
+
+    /// Converts a color to a dye, returning `Ok(_)` for an exact match, or `Err(_)` for the closest match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, Rgb};
+    /// # use std::convert::identity;
+    ///
+    /// assert_eq!(Dye::AppleGreen.color(), Rgb::new(155, 179, 99));
+    /// assert_eq!(Dye::try_from(Rgb::new(155, 179, 99)), Ok(Dye::AppleGreen));
+    /// assert_eq!(Dye::try_from(Rgb::new(155, 179, 98)), Err(Dye::AppleGreen));
+    ///
+    /// // use `std::convert::identity` if you don't care about exact matches
+    /// assert_eq!(Dye::try_from(Rgb::WHITE).unwrap_or_else(identity), Dye::LotusPink);
+    /// assert_eq!(Dye::try_from(Rgb::BLACK).unwrap_or_else(identity), Dye::InkBlue);
+    /// ```
+    fn try_from(value: Rgb) -> Result<Dye, Self::Error> {
+        let dye = Dye::VALUES.into_iter().min_by_key(|d| d.color().distance(value)).unwrap();
+        
+        if dye.color() == value {
+            Ok(dye)
+        }
+        else {
+            Err(dye)
+        }
+    }
+}
+
+impl FromStr for Dye {
+    /// The type returned if no [`Dye`] has a given short name.
+    type Err = ParseDyeError;
+
+    /// Parses the [short name](Dye::short_name) of a `Dye`. Unlike the localized [`Dye::from_str`], this
+    /// doesn't require the `fluent` feature, and is case-sensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Dye, ParseDyeError};
+    ///
+    /// assert_eq!("opo-opo-brown".parse(), Ok(Dye::OpoOpoBrown));
+    /// assert_eq!(Dye::SnowWhite.short_name().parse(), Ok(Dye::SnowWhite));
+    ///
+    /// assert_eq!("Opo-opo Brown".parse::<Dye>(), Err(ParseDyeError));
+    /// ```
+    fn from_str(s: &str) -> Result<Dye, ParseDyeError> {
+        Dye::VALUES.into_iter().find(|dye| dye.short_name() == s).ok_or(ParseDyeError)
+    }
+}
+
+/// Normalizes a color name for [`Dye::from_str_exact`] and [`Dye::search_prefix`]: lowercased, with `ß` replaced by `ss`
+/// and `’` by `'`.
+#[cfg(feature = "fluent")]
+fn normalize_name(name: &str) -> String {
+    name.replace('ß', "ss").replace('’', "'").to_lowercase()
+}
+
+/// Folds a color name for [`Dye::from_str`]: lowercased, without Latin diacritics, apostrophes nor whitespace,
+/// and with `ß` replaced by `ss`.
+#[cfg(feature = "fluent")]
+fn fold_name(name: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    name.nfd()
+        .filter(|c| !matches!(c, '\u{0300}'..='\u{036F}' | '\'' | '’') && !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .replace('ß', "ss")
+}
+
+/// An error that can be returned when parsing the short name of a [`Dye`].
+///
+/// This error is used as the error type for the [`FromStr`] implementation of [`Dye`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ParseDyeError;
+
+impl fmt::Display for ParseDyeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown dye short name")
+    }
+}
+
+impl Error for ParseDyeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count() {
+        assert_eq!(Dye::COUNT, Dye::VALUES.len());
+        assert_eq!(Category::COUNT, Category::VALUES.len());
+    }
+
+    #[test]
+    fn dyes_in_self_category() {
+        assert_eq!(Dye::VALUES.len(), Category::VALUES.iter().map(|category| category.dyes().len()).sum::<usize>());
+
+        for category in Category::VALUES {
+            assert!(category.dyes().iter().all(|dye| dye.category() == category));
+        }
+    }
+
+    #[test]
     fn dye_epsilon() {
         let mut epsilon = u32::MAX;
         
@@ -205,4 +1040,376 @@ mod test {
 
         assert_eq!(epsilon, Dye::EPSILON);
     }
+
+    #[test]
+    fn classify_category() {
+        for category in Category::VALUES {
+            assert_eq!(Category::classify(category.color()), (category, 1.0));
+        }
+
+        let (category, confidence) = Category::classify(Rgb::new(20, 20, 240));
+        assert_eq!(category, Category::Blue);
+        assert!((0.0..1.0).contains(&confidence), "{confidence}");
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn nearest_simd() {
+        use std::convert::identity;
+
+        // xorshift32
+        let mut state = 0x9E37_79B9_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let color = Rgb::from(next());
+            assert_eq!(Dye::nearest_simd(color), Dye::try_from(color).unwrap_or_else(identity), "{color:?}");
+        }
+
+        for dye in Dye::VALUES {
+            assert_eq!(Dye::nearest_simd(dye.color()), dye);
+        }
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn localized_dyes() {
+        use crate::Lang;
+
+        for lang in Lang::VALUES {
+            let bundle = lang.into_bundle();
+
+            for category in Category::VALUES {
+                let dyes = category.localized_dyes(&bundle);
+
+                assert_eq!(dyes.len(), category.dyes().len());
+                assert!(dyes.iter().all(|(dye, name)| dye.category() == category && !name.is_empty()));
+            }
+        }
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn search_prefix() {
+        use crate::Lang;
+
+        for lang in Lang::VALUES {
+            let bundle = lang.into_bundle();
+
+            assert_eq!(Dye::search_prefix(&bundle, "").len(), Dye::COUNT);
+
+            for dye in Dye::VALUES {
+                let name = dye.color_name(&bundle);
+
+                assert!(Dye::search_prefix(&bundle, name).contains(&dye), "{name}");
+                assert!(Dye::search_prefix(&bundle, &name.to_uppercase()).contains(&dye), "{name}");
+                assert_eq!(Dye::search_prefix(&bundle, name).first().copied(), Dye::from_str_exact(&bundle, &normalize_name(name)), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "fluent")]
+    fn from_str() {
+        use crate::Lang;
+
+        for lang in Lang::VALUES {
+            let bundle = lang.into_bundle();
+
+            for dye in Dye::VALUES {
+                let name = dye.color_name(&bundle);
+
+                assert_eq!(Dye::from_str(&bundle, name), Some(dye), "{name}");
+                assert_eq!(Dye::from_str(&bundle, &name.to_uppercase()), Some(dye), "{name}");
+                assert_eq!(Dye::from_str(&bundle, &name.replace(' ', "")), Some(dye), "{name}");
+            }
+        }
+
+        let fr = Lang::French.into_bundle();
+        assert_eq!(Dye::from_str(&fr, "Jaune Desert"), Some(Dye::DesertYellow));
+        assert_eq!(Dye::from_str(&fr, "vert celeste"), Some(Dye::CelesteGreen));
+    }
+
+    #[test]
+    fn from_color_within() {
+        for dye in Dye::VALUES {
+            assert_eq!(Dye::from_color_within(dye.color(), 0), Some(dye));
+        }
+
+        let color = Rgb::new(20, 20, 240);
+        let distance = Dye::try_from(color).unwrap_err().color().distance(color);
+
+        assert_eq!(Dye::from_color_within(color, distance - 1), None);
+        assert_eq!(Dye::from_color_within(color, distance), Some(Dye::try_from(color).unwrap_err()));
+        assert_eq!(Dye::from_color_within(color, u32::MAX), Some(Dye::try_from(color).unwrap_err()));
+
+        // a slightly off pixel is kept, while a background pixel is ignored
+        let near = Rgb::new(Dye::AppleGreen.color().r + 2, Dye::AppleGreen.color().g - 1, Dye::AppleGreen.color().b);
+        assert_eq!(Dye::from_color_within(near, 9), Some(Dye::AppleGreen));
+        assert_eq!(Dye::from_color_within(near, 4), None);
+
+        let background = Rgb::new(0, 255, 0);
+        assert_eq!(Dye::try_from(background).ok(), None);
+        assert_eq!(Dye::from_color_within(background, Dye::EPSILON), None);
+    }
+
+    #[test]
+    fn try_from_u8() {
+        for dye in Dye::VALUES {
+            assert_eq!(Dye::try_from(dye as u8), Ok(dye));
+        }
+
+        for category in Category::VALUES {
+            assert_eq!(Category::try_from(category as u8), Ok(category));
+        }
+
+        for n in Dye::VALUES.len()..=usize::from(u8::MAX) {
+            let n = u8::try_from(n).unwrap();
+            assert_eq!(Dye::try_from(n), Err(n));
+        }
+
+        assert_eq!(Category::try_from(7), Err(7));
+        assert_eq!(Category::try_from(u8::MAX), Err(u8::MAX));
+    }
+
+    #[test]
+    fn lighter_darker() {
+        let mut dye = Dye::SootBlack;
+        let mut steps = 0;
+
+        while let Some(lighter) = dye.lighter() {
+            assert!(lighter.luma() > dye.luma(), "{lighter:?} is not lighter than {dye:?}");
+            dye = lighter;
+            steps += 1;
+        }
+
+        assert_eq!(Some(dye.luma()), Dye::VALUES.iter().map(|dye| dye.luma()).max());
+        assert!(steps > 0 && steps < Dye::VALUES.len());
+
+        while let Some(darker) = dye.darker() {
+            assert!(darker.luma() < dye.luma(), "{darker:?} is not darker than {dye:?}");
+            dye = darker;
+        }
+
+        assert_eq!(Some(dye.luma()), Dye::VALUES.iter().map(|dye| dye.luma()).min());
+    }
+
+    #[test]
+    fn gradient() {
+        let (from, to) = (Dye::MesaRed, Dye::CeruleumBlue);
+        let gradient = from.gradient(to, 3);
+
+        assert_eq!(gradient, [from.color(), from.color().lerp(to.color(), 0.5), to.color()]);
+        assert_eq!(gradient.first().copied().map(Dye::try_from), Some(Ok(from)));
+        assert_eq!(gradient.last().copied().map(Dye::try_from), Some(Ok(to)));
+
+        assert_eq!(from.gradient(to, 2), [from.color(), to.color()]);
+        assert_eq!(from.gradient(from, 4), [from.color(); 4]);
+    }
+
+    #[test]
+    fn ord() {
+        let mut dyes = Dye::VALUES;
+        dyes.sort();
+
+        let mut by_luma = Dye::VALUES;
+        by_luma.sort_by_key(|dye| dye.luma());
+
+        assert_eq!(dyes, by_luma);
+        assert!(dyes.iter().zip(dyes.iter().skip(1)).all(|(a, b)| a.luma() < b.luma() || (a.luma() == b.luma() && (*a as u8) < (*b as u8))));
+
+        let set: std::collections::BTreeSet<Dye> = Dye::VALUES.into_iter().rev().collect();
+        assert!(set.into_iter().eq(dyes));
+    }
+
+    #[test]
+    fn from_hex() {
+        for dye in Dye::VALUES {
+            assert_eq!(Dye::from_hex(&dye.color().to_string()), Ok(Ok(dye)));
+            assert_eq!(Dye::from_hex(&dye.color().to_string()).map(|result| (result.unwrap_or_else(|dye| dye), result.is_ok())), Dye::parse_nearest(&dye.color().to_string()));
+        }
+
+        assert_eq!(Dye::from_hex("#000"), Ok(Err(Dye::InkBlue)));
+        assert_eq!(Dye::from_hex("#00000"), Err(ParseHexError::BadLen));
+    }
+
+    #[test]
+    fn query() {
+        let blues = Dye::query(Some(Category::Blue), 80..=160);
+
+        assert!(!blues.is_empty());
+        assert!(blues.iter().all(|dye| dye.category() == Category::Blue && (80..=160).contains(&dye.luma())));
+        assert_eq!(blues.len(), Category::Blue.dyes().iter().filter(|dye| (80..=160).contains(&dye.luma())).count());
+
+        assert_eq!(Dye::query(None, 80..=160).len(), Dye::VALUES.iter().filter(|dye| (80..=160).contains(&dye.luma())).count());
+        assert_eq!(Dye::query(Some(Category::Blue), 0..=u8::MAX), Category::Blue.dyes());
+    }
+
+    #[test]
+    fn to_rgba_bytes() {
+        for dye in Dye::VALUES {
+            for alpha in [0, 127, 255] {
+                let [r, g, b, a] = dye.to_rgba_bytes(alpha);
+
+                assert_eq!(Rgb::new(r, g, b), dye.color());
+                assert_eq!(a, alpha);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest() {
+        for category in Category::VALUES {
+            assert_eq!(Category::nearest(category.color()), category);
+        }
+
+        assert_eq!(Category::nearest(Rgb::new(40, 40, 240)), Category::Blue);
+        assert_eq!(Category::nearest(Dye::SnowWhite.color()), Category::White);
+        assert_eq!(Category::nearest(Dye::MidnightBlue.color()), Category::Brown);
+    }
+
+    #[test]
+    fn nearest_by_dyes() {
+        for dye in Dye::VALUES {
+            assert_eq!(Category::nearest_by_dyes(dye.color()), dye.category());
+        }
+
+        // the two functions disagree on most dark dyes
+        assert_ne!(Category::nearest_by_dyes(Dye::MidnightBlue.color()), Category::nearest(Dye::MidnightBlue.color()));
+        assert_eq!(Category::nearest_by_dyes(Rgb::new(40, 40, 240)), Category::Blue);
+    }
+
+    #[test]
+    fn centroid() {
+        for category in Category::VALUES {
+            let nearest = Dye::try_from(category.centroid()).unwrap_or_else(|dye| dye).category();
+
+            match category {
+                Category::Red | Category::Yellow => assert_eq!(nearest, Category::Brown),
+                Category::Purple => assert_eq!(nearest, Category::Red),
+                _ => assert_eq!(nearest, category)
+            }
+        }
+    }
+
+    #[test]
+    fn confusable_pairs() {
+        for cvd in [None, Some(CvdKind::Protanopia), Some(CvdKind::Deuteranopia), Some(CvdKind::Tritanopia)] {
+            assert!(Dye::confusable_pairs(0.5, cvd).is_empty(), "{cvd:?}");
+            assert!(Dye::confusable_pairs(20.0, cvd).len() > 500, "{cvd:?}");
+        }
+
+        // no two dyes are barely distinguishable, unless the red-green axis is lost
+        assert!(Dye::confusable_pairs(2.0, None).is_empty());
+        assert!(!Dye::confusable_pairs(2.0, Some(CvdKind::Protanopia)).is_empty());
+        assert!(!Dye::confusable_pairs(2.0, Some(CvdKind::Deuteranopia)).is_empty());
+
+        assert_eq!(Dye::confusable_pairs(f32::INFINITY, None).len(), 85 * 84 / 2);
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn value_enum() {
+        use clap::ValueEnum;
+
+        assert_eq!(<Dye as ValueEnum>::from_str("snow-white", false), Ok(Dye::SnowWhite));
+        assert_eq!(<Dye as ValueEnum>::from_str("snow white", false).ok(), None);
+
+        for dye in Dye::VALUES {
+            assert_eq!(<Dye as ValueEnum>::from_str(dye.short_name(), false), Ok(dye));
+        }
+    }
+
+    #[test]
+    fn top_k_nearest() {
+        for color in [Rgb::BLACK, Rgb::WHITE, Rgb::new(155, 179, 101), Rgb::new(91, 206, 250), Dye::InkBlue.color()] {
+            let nearest = Dye::top_k_nearest(color, 10);
+            let first = Dye::try_from(color).unwrap_or_else(|dye| dye);
+
+            assert_eq!(nearest.len(), 10);
+            assert_eq!(nearest[0], (first, first.color().distance(color)));
+            assert!(nearest.iter().zip(nearest.iter().skip(1)).all(|(a, b)| a.1 <= b.1), "{nearest:?}");
+        }
+
+        assert_eq!(Dye::top_k_nearest(Rgb::BLACK, 0), []);
+        assert_eq!(Dye::top_k_nearest(Rgb::BLACK, usize::MAX).len(), Dye::VALUES.len());
+    }
+
+    #[test]
+    fn stain() {
+        for dye in Dye::VALUES {
+            for color in [Rgb::BLACK, Rgb::WHITE, Rgb::new(91, 206, 250)] {
+                assert_eq!(color.stain(dye, 0.0), color);
+                assert_eq!(color.stain(dye, 1.0), dye.color());
+                assert_eq!(color.stain(dye, 2.0), dye.color());
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_perceptual() {
+        for dye in Dye::VALUES {
+            assert_eq!(Dye::nearest_perceptual(dye.color()), dye);
+        }
+
+        let color = Rgb::new(91, 206, 250);
+        let nearest = Dye::nearest_perceptual(color);
+        assert!(Dye::VALUES.iter().all(|dye| nearest.color().delta_e(color) <= dye.color().delta_e(color)));
+    }
+
+    #[test]
+    fn values_by_luma() {
+        let mut dyes = Dye::VALUES;
+        dyes.sort_by_key(|dye| 255 - dye.luma());
+
+        assert_eq!(Dye::VALUES_BY_LUMA, dyes);
+    }
+
+    #[test]
+    fn to_web_safe() {
+        for (dye, expected) in [
+            (Dye::SnowWhite, Rgb::gray(204)),
+            (Dye::SootBlack, Rgb::gray(51)),
+            (Dye::DalamudRed, Rgb::new(102, 51, 51)),
+            (Dye::AppleGreen, Rgb::new(153, 204, 102)),
+            (Dye::CoeurlYellow, Rgb::new(204, 153, 0))
+        ] {
+            assert_eq!(dye.color().to_web_safe(), expected, "{dye:?}");
+        }
+
+        for dye in Dye::VALUES {
+            let safe = dye.color().to_web_safe();
+
+            assert!([safe.r, safe.g, safe.b].iter().all(|c| c % 51 == 0));
+            assert_eq!(safe.to_web_safe(), safe);
+            assert!(dye.color().manhattan_distance(safe) <= 3 * 25);
+        }
+    }
+
+    #[test]
+    fn short_name_round_trip() {
+        for dye in Dye::VALUES {
+            assert_eq!(dye.short_name().parse(), Ok(dye));
+            assert_eq!(dye.short_name().to_uppercase().parse::<Dye>(), Err(ParseDyeError));
+        }
+
+        assert_eq!("".parse::<Dye>(), Err(ParseDyeError));
+    }
+
+    #[test]
+    fn sorted() {
+        let dyes = Dye::sorted_by_luma();
+        assert_eq!(dyes[0], Dye::LotusPink);
+        assert_eq!(dyes.iter().find(|dye| dye.category() == Category::White), Some(&Dye::SnowWhite));
+        assert!(dyes.iter().zip(dyes.iter().skip(1)).all(|(a, b)| a.luma() >= b.luma()));
+
+        let dyes = Dye::sorted_by_hue();
+        assert!(dyes.iter().zip(dyes.iter().skip(1)).all(|(a, b)| a.color().to_hsl().0 <= b.color().to_hsl().0));
+        assert!(Dye::VALUES.iter().all(|dye| dyes.contains(dye)));
+    }
 }
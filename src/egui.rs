@@ -0,0 +1,54 @@
+use ::egui::Color32;
+
+use crate::{Dye, Rgb};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "egui")))]
+impl From<Rgb> for Color32 {
+    /// Converts this color to an opaque [`Color32`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    /// use egui::Color32;
+    ///
+    /// assert_eq!(Color32::from(Rgb::new(91, 206, 250)), Color32::from_rgb(91, 206, 250));
+    /// ```
+    #[inline]
+    fn from(value: Rgb) -> Color32 {
+        Color32::from_rgb(value.r, value.g, value.b)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "egui")))]
+impl From<Dye> for Color32 {
+    /// Converts the color of this dye to an opaque [`Color32`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    /// use egui::Color32;
+    ///
+    /// assert_eq!(Color32::from(Dye::SnowWhite), Color32::from(Dye::SnowWhite.color()));
+    /// ```
+    #[inline]
+    fn from(value: Dye) -> Color32 {
+        Color32::from(value.color())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn color32() {
+        for dye in Dye::VALUES {
+            let color = dye.color();
+
+            assert_eq!(Color32::from(dye).to_array(), [color.r, color.g, color.b, 255]);
+            assert_eq!(Color32::from(color), Color32::from(dye));
+        }
+    }
+}
@@ -1,17 +1,20 @@
 #![cfg(feature = "fluent")]
 
 use std::borrow::{Borrow, Cow};
+use std::env;
 use std::error::Error;
 use std::fmt::{self, Formatter};
 use std::str::FromStr;
 
-use fluent::{FluentArgs, FluentResource};
+use fluent::{FluentArgs, FluentError, FluentResource, FluentValue};
 use fluent::memoizer::MemoizerKind;
 use fluent::resolver::Scope;
 use fluent_syntax::parser::ParserError;
 use log::error;
 use unic_langid::{langid, LanguageIdentifier};
 
+use crate::{Category, Dye, Snack};
+
 /// Formats a Fluent message fail-safely. Missing keys are formatted arbitrarily.
 ///
 /// Messages without arguments are evaluated to [`&str`], while messages with arguments
@@ -98,6 +101,91 @@ pub fn __format_message<'a, R, M>(bundle: &'a fluent::bundle::FluentBundle<R, M>
     })
 }
 
+/// Returns every message key used by this crate and its examples.
+///
+/// This includes the short names of all snacks, dyes and categories, followed by the user interface strings.
+/// Useful for checking that a translation is complete.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, message_keys};
+///
+/// assert!(message_keys().contains(&Dye::SnowWhite.short_name()));
+/// assert!(message_keys().contains(&"required-fruits"));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[must_use]
+pub const fn message_keys() -> &'static [&'static str] {
+    /// Keys that are not the short name of a snack, a dye or a category.
+    const UI_KEYS: [&str; 8] = [
+        "lang-input",
+        "starting-color-input",
+        "final-color-input",
+        "required-fruits",
+        "feed-order",
+        "total-fruits",
+        "none",
+        "han-lemon-note"
+    ];
+
+    const LEN: usize = Snack::VALUES.len() + Dye::VALUES.len() + Category::VALUES.len() + UI_KEYS.len();
+
+    const KEYS: [&str; LEN] = {
+        let mut keys = [""; LEN];
+        let mut i = 0;
+
+        macro_rules! push {
+            ($values:expr, $key:expr) => {{
+                let values = $values;
+
+                let mut j = 0;
+                while j < values.len() {
+                    keys[i] = $key(values[j]);
+                    i += 1;
+                    j += 1;
+                }
+            }};
+        }
+
+        push!(Snack::VALUES, Snack::short_name);
+        push!(Dye::VALUES, Dye::short_name);
+        push!(Category::VALUES, Category::short_name);
+        push!(UI_KEYS, std::convert::identity);
+
+        keys
+    };
+
+    &KEYS
+}
+
+/// Returns the message of `key` in every [`Lang`], in the order of [`Lang::VALUES`].
+///
+/// Returns an empty vector if `key` is not one of the [`message_keys`]. Messages expecting arguments,
+/// such as the snacks' quantified names, are formatted like [`message!`] does when arguments are missing.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{all_translations, Lang};
+///
+/// let translations = all_translations("sky-blue");
+///
+/// assert_eq!(translations.len(), 4);
+/// assert_eq!(translations[0], (Lang::English, "Sky Blue".to_owned()));
+///
+/// assert!(all_translations("chocobo-yellow").is_empty());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[must_use]
+pub fn all_translations(key: &str) -> Vec<(Lang, String)> {
+    let Some(key) = message_keys().iter().copied().find(|k| *k == key) else {
+        return Vec::new();
+    };
+
+    Lang::VALUES.into_iter().map(|lang| (lang, message!(&lang.into_bundle(), key).to_owned())).collect()
+}
+
 /// A language officially supported by *Final Fantasy XIV*.
 /// Can be converted into a [`FluentBundle`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -111,8 +199,11 @@ pub enum Lang {
 }
 
 impl Lang {
+    /// The number of `Lang` variants, four.
+    pub const COUNT: usize = 4;
+
     /// Contains all four `Lang` variants.
-    pub const VALUES: [Lang; 4] = [
+    pub const VALUES: [Lang; Lang::COUNT] = [
         Lang::English,
         Lang::French,
         Lang::German,
@@ -151,7 +242,118 @@ impl Lang {
         }
     }
 
+    /// Returns the language of a POSIX locale such as `fr_FR.UTF-8`, or of a BCP 47 language tag such as `ja-JP`,
+    /// or `None` if it is not supported.
+    ///
+    /// Only the language is considered; the territory, codeset and modifier are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    ///
+    /// assert_eq!(Lang::from_locale("fr_FR.UTF-8"), Some(Lang::French));
+    /// assert_eq!(Lang::from_locale("ja-JP"), Some(Lang::Japanese));
+    /// assert_eq!(Lang::from_locale("en"), Some(Lang::English));
+    /// assert_eq!(Lang::from_locale("C"), None);
+    /// ```
+    #[must_use]
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale.split(['_', '-', '.', '@']).next().unwrap_or_default();
+
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::English),
+            "fr" => Some(Lang::French),
+            "de" => Some(Lang::German),
+            "ja" | "jp" => Some(Lang::Japanese),
+            _ => None
+        }
+    }
+
+    /// Returns the language of the user's locale, or `None` if it is not supported.
+    ///
+    /// Like POSIX, the locale is read from the first non-empty `LC_ALL`, `LC_MESSAGES` or `LANG` environment variable.
+    /// On Windows, the user default locale is used if none of these variables are set.
+    /// The locale is then resolved with [`Lang::from_locale`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    ///
+    /// let lang = Lang::from_env().unwrap_or(Lang::English);
+    /// ```
+    #[must_use]
+    pub fn from_env() -> Option<Lang> {
+        let locale = posix_locale(|key| env::var(key).ok());
+
+        #[cfg(windows)]
+        let locale = locale.or_else(user_default_locale);
+
+        locale.as_deref().and_then(Lang::from_locale)
+    }
+
+    /// Returns `true` if `self` is written from right to left.
+    ///
+    /// No language of *Final Fantasy XIV* is currently written from right to left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    ///
+    /// assert!(!Lang::English.is_rtl());
+    /// ```
+    #[must_use]
+    pub const fn is_rtl(self) -> bool {
+        match self {
+            Lang::English | Lang::French | Lang::German | Lang::Japanese => false
+        }
+    }
+
+    /// Returns the character separating groups of thousands in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    ///
+    /// assert_eq!(Lang::English.group_separator(), ',');
+    /// assert_eq!(Lang::French.group_separator(), '\u{202F}');
+    /// ```
+    #[must_use]
+    pub const fn group_separator(self) -> char {
+        match self {
+            Lang::English | Lang::Japanese => ',',
+            Lang::French => '\u{202F}', // narrow no-break space
+            Lang::German => '.'
+        }
+    }
+
     /// Returns the Fluent translation resource of `self`.
+    ///
+    /// The resource calls the `NUMBER` function, which is not built into Fluent. Bundles not created with
+    /// [`Lang::into_bundle`] must register it with [`Lang::add_functions`], otherwise the quantities are not formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    /// use fluent::{FluentArgs, FluentResource};
+    /// use fluent::concurrent::FluentBundle;
+    ///
+    /// let mut bundle = FluentBundle::new_concurrent(vec![Lang::English.langid()]);
+    /// Lang::English.add_functions(&mut bundle).unwrap();
+    /// bundle.add_resource(FluentResource::try_new(Lang::English.file().to_owned()).unwrap()).unwrap();
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("quantity", 1234);
+    ///
+    /// let pattern = bundle.get_message("apple").and_then(|msg| msg.value()).unwrap();
+    /// let apples = bundle.format_pattern(pattern, Some(&args), &mut Vec::new());
+    ///
+    /// assert_eq!(apples, "\u{2068}1,234\u{2069} Xelphatol Apples");
+    /// ```
     #[must_use]
     pub const fn file(self) -> &'static str {
         match self {
@@ -162,6 +364,48 @@ impl Lang {
         }
     }
 
+    /// Registers the Fluent functions called by the [translation resource](Lang::file) of `self` into `bundle`,
+    /// i.e. `NUMBER`, which groups the thousands of integers with the [separator](Lang::group_separator) of `self`.
+    ///
+    /// This is already done by [`Lang::into_bundle`]; it is only needed for bundles created otherwise,
+    /// e.g. [concurrent](fluent::concurrent::FluentBundle) ones.
+    ///
+    /// Returns an error if `bundle` already has a function with the same name.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn add_functions<R, M>(self, bundle: &mut fluent::bundle::FluentBundle<R, M>) -> Result<(), FluentError> {
+        let separator = self.group_separator();
+
+        bundle.add_function("NUMBER", move |positional, _| number(positional, separator))
+    }
+
+    /// Checks that the translation resource of `self` defines a message for every key of [`message_keys`].
+    ///
+    /// Returns the missing keys on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Lang;
+    ///
+    /// assert_eq!(Lang::Japanese.validate(), Ok(()));
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    pub fn validate(self) -> Result<(), Vec<String>> {
+        let bundle = self.into_bundle();
+
+        let missing: Vec<String> = message_keys().iter()
+            .filter(|key| !bundle.has_message(key))
+            .map(|key| (*key).to_owned())
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(missing)
+        }
+    }
+
     /// Parses the translation resource of `self` into a new [`FluentBundle`].
     /// Returns an empty bundle on error, but this shouldn't happen since the file is located in the read-only data segment.
     #[must_use]
@@ -197,11 +441,65 @@ impl TryFrom<Lang> for FluentBundle {
         let mut bundle = FluentBundle::new(vec![value.langid()]);
         let res = FluentResource::try_new(value.file().to_owned())?;
 
+        if let Err(e) = value.add_functions(&mut bundle) {
+            error!(target: "lang", "{e}");
+        }
+
         bundle.add_resource_overriding(res);
         Ok(bundle)
     }
 }
 
+/// The `NUMBER` Fluent function, formatting integers with their thousands grouped by `separator`.
+/// Other values are returned as is.
+#[allow(clippy::cast_possible_truncation)]
+fn number<'a>(positional: &[FluentValue<'a>], separator: char) -> FluentValue<'a> {
+    match positional {
+        [FluentValue::Number(n)] if n.value.fract() == 0.0 && n.value.abs() < 1e15 => {
+            let digits = (n.value as i64).unsigned_abs().to_string();
+
+            let mut s = String::with_capacity(digits.len() * 2);
+            if n.value < 0.0 {
+                s.push('-');
+            }
+
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i) % 3 == 0 {
+                    s.push(separator);
+                }
+
+                s.push(digit);
+            }
+
+            FluentValue::String(Cow::Owned(s))
+        },
+        [value, ..] => value.clone(),
+        [] => FluentValue::Error
+    }
+}
+
+/// Returns the value of the first non-empty `LC_ALL`, `LC_MESSAGES` or `LANG` variable, read with `var`.
+fn posix_locale(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+        .find_map(|key| var(key).filter(|value| !value.is_empty()))
+}
+
+/// Returns the user default locale name, e.g. `fr-FR`.
+#[cfg(windows)]
+fn user_default_locale() -> Option<String> {
+    use windows_sys::Win32::Globalization::GetUserDefaultLocaleName;
+
+    // `LOCALE_NAME_MAX_LENGTH`
+    let mut buf = [0_u16; 85];
+
+    // SAFETY: `buf` can hold 85 UTF-16 code units.
+    let len = unsafe { GetUserDefaultLocaleName(buf.as_mut_ptr(), 85) };
+
+    // the returned length includes the null terminator, and is zero on failure
+    let name = buf.get(..usize::try_from(len).ok()?.checked_sub(1)?)?;
+    Some(String::from_utf16_lossy(name))
+}
+
 impl fmt::Display for Lang {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(self.short_code())
@@ -256,3 +554,114 @@ impl fmt::Display for ParseLangError {
 }
 
 impl Error for ParseLangError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count() {
+        assert_eq!(Lang::COUNT, Lang::VALUES.len());
+    }
+
+    #[test]
+    fn posix_locale() {
+        let locale = |vars: &[(&str, &str)]| {
+            super::posix_locale(|key| vars.iter().find(|(k, _)| *k == key).map(|(_, value)| (*value).to_owned()))
+        };
+
+        assert_eq!(locale(&[("LANG", "fr_FR.UTF-8")]).as_deref().and_then(Lang::from_locale), Some(Lang::French));
+        assert_eq!(locale(&[("LC_ALL", "de_DE.UTF-8"), ("LANG", "fr_FR.UTF-8")]).as_deref().and_then(Lang::from_locale), Some(Lang::German));
+        assert_eq!(locale(&[("LC_MESSAGES", "ja_JP.UTF-8"), ("LANG", "fr_FR.UTF-8")]).as_deref(), Some("ja_JP.UTF-8"));
+        assert_eq!(locale(&[("LC_ALL", ""), ("LANG", "C")]).as_deref().and_then(Lang::from_locale), None);
+        assert_eq!(locale(&[("LC_ALL", ""), ("LANG", "C")]).as_deref(), Some("C"));
+        assert_eq!(locale(&[("LC_ALL", ""), ("LC_MESSAGES", "")]), None);
+        assert_eq!(locale(&[]), None);
+    }
+
+    #[test]
+    fn no_rtl_lang() {
+        for lang in Lang::VALUES {
+            assert!(!lang.is_rtl(), "{lang}");
+        }
+    }
+
+    #[test]
+    fn langs_validate() {
+        for lang in Lang::VALUES {
+            assert_eq!(lang.validate(), Ok(()), "{lang}");
+        }
+    }
+
+    #[test]
+    fn quantities_are_grouped() {
+        for (lang, expected) in [
+            (Lang::English,  "1,234"),
+            (Lang::French,   "1\u{202F}234"),
+            (Lang::German,   "1.234"),
+            (Lang::Japanese, "1,234")
+        ] {
+            let bundle = lang.into_bundle();
+
+            for snack in Snack::VALUES {
+                let name = snack.quantified_name(&bundle, 1234);
+                assert!(name.contains(&format!("\u{2068}{expected}\u{2069}")), "{name:?}");
+            }
+
+            assert!(Snack::Apple.quantified_name(&bundle, 999).contains("\u{2068}999\u{2069}"));
+            assert!(Snack::Apple.quantified_name(&bundle, 1_000_000).contains(&format!("1{0}000{0}000", lang.group_separator())));
+        }
+    }
+
+    #[test]
+    fn file_with_functions() {
+        for (lang, expected) in [
+            (Lang::English,  "1,234"),
+            (Lang::French,   "1\u{202F}234"),
+            (Lang::German,   "1.234"),
+            (Lang::Japanese, "1,234")
+        ] {
+            let mut bundle = fluent::concurrent::FluentBundle::new_concurrent(vec![lang.langid()]);
+            assert_eq!(lang.add_functions(&mut bundle), Ok(()));
+            assert!(lang.add_functions(&mut bundle).is_err());
+
+            bundle.add_resource(FluentResource::try_new(lang.file().to_owned()).unwrap()).unwrap();
+
+            let mut args = FluentArgs::new();
+            args.set("quantity", 1234);
+
+            let mut errors = Vec::new();
+            let pattern = bundle.get_message("apple").and_then(|msg| msg.value()).unwrap();
+            let apples = bundle.format_pattern(pattern, Some(&args), &mut errors);
+
+            assert_eq!(errors, []);
+            assert!(apples.contains(&format!("\u{2068}{expected}\u{2069}")), "{apples:?}");
+        }
+    }
+
+    #[test]
+    fn translations_are_complete() {
+        for lang in Lang::VALUES {
+            let bundle = lang.into_bundle();
+
+            for key in message_keys() {
+                assert!(bundle.has_message(key), "`{}` is missing `{key}`", lang.short_code());
+            }
+        }
+    }
+
+    #[test]
+    fn all_translations() {
+        let translations = super::all_translations("sky-blue");
+
+        assert_eq!(translations.iter().map(|(lang, _)| *lang).collect::<Vec<_>>(), Lang::VALUES);
+        assert_eq!(translations[0].1, "Sky Blue");
+        assert_eq!(translations.iter().find(|(lang, _)| *lang == Lang::French).map(|(_, s)| s.as_str()), Some(Dye::SkyBlue.color_name(&Lang::French.into_bundle())));
+
+        for key in message_keys() {
+            assert!(super::all_translations(key).iter().all(|(_, s)| !s.is_empty()), "{key}");
+        }
+
+        assert_eq!(super::all_translations(""), []);
+    }
+}
@@ -2,44 +2,44 @@
 
 apple =
     { $quantity ->
-         [one] { $quantity } Xelphatol-Apfel
-        *[other] { $quantity } Xelphatol-Äpfel
+         [one] { NUMBER($quantity) } Xelphatol-Apfel
+        *[other] { NUMBER($quantity) } Xelphatol-Äpfel
     }
 
 pear =
     { $quantity ->
-         [one] { $quantity } Mamook-Birne
-        *[other] { $quantity } Mamook-Birnen
+         [one] { NUMBER($quantity) } Mamook-Birne
+        *[other] { NUMBER($quantity) } Mamook-Birnen
     }
 
 berries =
     { $quantity ->
-         [one] { $quantity } O’Ghomoro-Beere
-        *[other] { $quantity } O’Ghomoro-Beeren
+         [one] { NUMBER($quantity) } O’Ghomoro-Beere
+        *[other] { NUMBER($quantity) } O’Ghomoro-Beeren
     }
 
 plum =
     { $quantity ->
-         [one] { $quantity } Doma-Pflaume
-        *[other] { $quantity } Doma-Pflaumen
+         [one] { NUMBER($quantity) } Doma-Pflaume
+        *[other] { NUMBER($quantity) } Doma-Pflaumen
     }
 
 fruit =
     { $quantity ->
-         [one] { $quantity } Val-Frucht
-        *[other] { $quantity } Val-Früchte
+         [one] { NUMBER($quantity) } Val-Frucht
+        *[other] { NUMBER($quantity) } Val-Früchte
     }
 
 pineapple =
     { $quantity ->
-         [one] { $quantity } Cieldaläen-Ananas
-        *[other] { $quantity } Cieldaläen-Ananas
+         [one] { NUMBER($quantity) } Cieldaläen-Ananas
+        *[other] { NUMBER($quantity) } Cieldaläen-Ananas
     }
 
 lemon =
     { $quantity ->
-        [one] { $quantity } Han-Zitrone
-       *[other] { $quantity } Han-Zitronen
+        [one] { NUMBER($quantity) } Han-Zitrone
+       *[other] { NUMBER($quantity) } Han-Zitronen
     }
 
 ## Farben
@@ -154,6 +154,11 @@ final-color-input = Endfarbe:
 
 required-fruits = Benötigte Früchte:
 feed-order = Reihenfolge, in der die Früchte zu geben:
+total-fruits =
+    { $quantity ->
+        [one] Insgesamt: { NUMBER($quantity) } Frucht
+       *[other] Insgesamt: { NUMBER($quantity) } Früchte
+    }
 
 none = (keine)
 han-lemon-note = Hinweis: die Verwendung einer Han-Zitrone kann die Fruchtkosten um { $ratio }% senken.
@@ -2,44 +2,44 @@
 
 apple =
     { $quantity ->
-         [one] { $quantity } Xelphatol Apple
-        *[other] { $quantity } Xelphatol Apples
+         [one] { NUMBER($quantity) } Xelphatol Apple
+        *[other] { NUMBER($quantity) } Xelphatol Apples
     }
 
 pear =
     { $quantity ->
-         [one] { $quantity } Mamook Pear
-        *[other] { $quantity } Mamook Pears
+         [one] { NUMBER($quantity) } Mamook Pear
+        *[other] { NUMBER($quantity) } Mamook Pears
     }
 
 berries =
     { $quantity ->
-         [one] { $quantity } O’Ghomoro Berry
-        *[other] { $quantity } O’Ghomoro Berries
+         [one] { NUMBER($quantity) } O’Ghomoro Berry
+        *[other] { NUMBER($quantity) } O’Ghomoro Berries
     }
 
 plum =
     { $quantity ->
-         [one] { $quantity } Doman Plum
-        *[other] { $quantity } Doman Plums
+         [one] { NUMBER($quantity) } Doman Plum
+        *[other] { NUMBER($quantity) } Doman Plums
     }
 
 fruit =
     { $quantity ->
-         [one] { $quantity } Valfruit
-        *[other] { $quantity } Valfruits
+         [one] { NUMBER($quantity) } Valfruit
+        *[other] { NUMBER($quantity) } Valfruits
     }
 
 pineapple =
     { $quantity ->
-         [one] { $quantity } Cieldalaes Pineapple
-        *[other] { $quantity } Cieldalaes Pineapples
+         [one] { NUMBER($quantity) } Cieldalaes Pineapple
+        *[other] { NUMBER($quantity) } Cieldalaes Pineapples
     }
 
 lemon =
     { $quantity ->
-        [one] { $quantity } Han Lemon
-       *[other] { $quantity } Han Lemons
+        [one] { NUMBER($quantity) } Han Lemon
+       *[other] { NUMBER($quantity) } Han Lemons
     }
 
 ## Colors
@@ -154,6 +154,11 @@ final-color-input = Final Color:
 
 required-fruits = Required Fruits:
 feed-order = Feed Order:
+total-fruits =
+    { $quantity ->
+        [one] Total: { NUMBER($quantity) } fruit
+       *[other] Total: { NUMBER($quantity) } fruits
+    }
 
 none = (none)
 han-lemon-note = Note: using a Han Lemon can reduce the fruit cost by { $ratio }%.
@@ -2,44 +2,44 @@
 
 apple =
     { $quantity ->
-         [one] { $quantity } pomme de Xelphatol
-        *[other] { $quantity } pommes de Xelphatol
+         [one] { NUMBER($quantity) } pomme de Xelphatol
+        *[other] { NUMBER($quantity) } pommes de Xelphatol
     }
 
 pear =
     { $quantity ->
-         [one] { $quantity } poire alligator de Mamook
-        *[other] { $quantity } poires alligator de Mamook
+         [one] { NUMBER($quantity) } poire alligator de Mamook
+        *[other] { NUMBER($quantity) } poires alligator de Mamook
     }
 
 berries =
     { $quantity ->
-         [one] { $quantity } raisin d’O’Ghomoro
-        *[other] { $quantity } raisins d’O’Ghomoro
+         [one] { NUMBER($quantity) } raisin d’O’Ghomoro
+        *[other] { NUMBER($quantity) } raisins d’O’Ghomoro
     }
 
 plum =
     { $quantity ->
-         [one] { $quantity } prune de Doma
-        *[other] { $quantity } prunes de Doma
+         [one] { NUMBER($quantity) } prune de Doma
+        *[other] { NUMBER($quantity) } prunes de Doma
     }
 
 fruit =
     { $quantity ->
-         [one] { $quantity } groseille sanguine du Val
-        *[other] { $quantity } groseilles sanguines du Val
+         [one] { NUMBER($quantity) } groseille sanguine du Val
+        *[other] { NUMBER($quantity) } groseilles sanguines du Val
     }
 
 pineapple =
     { $quantity ->
-         [one] { $quantity } ananas des Cieldalaes
-        *[other] { $quantity } ananas des Cieldalaes
+         [one] { NUMBER($quantity) } ananas des Cieldalaes
+        *[other] { NUMBER($quantity) } ananas des Cieldalaes
     }
 
 lemon =
     { $quantity ->
-        [one] { $quantity } citron de Radz-at-Han
-       *[other] { $quantity } citrons de Radz-at-Han
+        [one] { NUMBER($quantity) } citron de Radz-at-Han
+       *[other] { NUMBER($quantity) } citrons de Radz-at-Han
    }
 
 ## Couleurs
@@ -154,6 +154,11 @@ final-color-input = Couleur finale :
 
 required-fruits = Fruits nécessaires :
 feed-order = Ordre dans lequel donner les fruits :
+total-fruits =
+    { $quantity ->
+        [one] Total : { NUMBER($quantity) } fruit
+       *[other] Total : { NUMBER($quantity) } fruits
+    }
 
 none = (aucun)
 han-lemon-note = Remarque : utiliser un citron de Radz-at-Han peut réduire le coût en fruits de { $ratio }%.
@@ -2,37 +2,37 @@
 
 apple =
     { $quantity ->
-        *[other] ゼルファトルの果実{ $quantity }個
+        *[other] ゼルファトルの果実{ NUMBER($quantity) }個
     }
 
 pear =
     { $quantity ->
-        *[other] マムークの果実{ $quantity }個
+        *[other] マムークの果実{ NUMBER($quantity) }個
     }
 
 berries =
     { $quantity ->
-        *[other] オ・ゴモロの果実{ $quantity }個
+        *[other] オ・ゴモロの果実{ NUMBER($quantity) }個
     }
 
 plum =
     { $quantity ->
-        *[other] ドマの果実{ $quantity }個
+        *[other] ドマの果実{ NUMBER($quantity) }個
     }
 
 fruit =
     { $quantity ->
-        *[other] バルの果実{ $quantity }個
+        *[other] バルの果実{ NUMBER($quantity) }個
     }
 
 pineapple =
     { $quantity ->
-        *[other] シェルダレーの果実{ $quantity }個
+        *[other] シェルダレーの果実{ NUMBER($quantity) }個
     }
 
 lemon =
     { $quantity ->
-       *[other] ラザハンの果実{ $quantity }個
+       *[other] ラザハンの果実{ NUMBER($quantity) }個
    }
 
 ## カラーズ
@@ -129,6 +129,16 @@ colibri-pink = コリブリピンク
 plum-purple = プラムパープル
 regal-purple = リーガルパープル
 
+## カテゴリー
+
+white = 白系カララント
+red = 赤系カララント
+brown = 茶系カララント
+yellow = 黄系カララント
+green = 緑系カララント
+blue = 青系カララント
+purple = 紫系カララント
+
 ## 組み込み例
 
 lang-input = 言語：
@@ -137,6 +147,7 @@ final-color-input = ファイナルカラー：
 
 required-fruits = 必要な果物：
 feed-order = 果実を与える順番：
+total-fruits = 合計：{ NUMBER($quantity) }個
 
 none = （なし）
 han-lemon-note = 注：ラザハンの果実を使用すると、果物のコストを{ $ratio }％削減できる。
@@ -0,0 +1,257 @@
+use crate::Rgb;
+
+impl Rgb {
+    /// Converts `self` into the [HSL](https://en.wikipedia.org/wiki/HSL_and_HSV) color space, as `(hue, saturation, lightness)`.
+    ///
+    /// The hue is in degrees, between `0.0` inclusive and `360.0` exclusive; the saturation and lightness are between
+    /// `0.0` and `1.0`. Grays have a saturation and a hue of `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.to_hsl(), (0.0, 1.0, 0.5));
+    /// assert_eq!(Rgb::CYAN.to_hsl(), (180.0, 1.0, 0.5));
+    /// assert_eq!(Rgb::WHITE.to_hsl(), (0.0, 0.0, 1.0));
+    /// ```
+    #[must_use]
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+
+        // `c / (1 - |2l - 1|)`, computed on integers to avoid rounding errors
+        let s = if max == min { 0.0 } else { f32::from(max - min) / f32::from(255 - (u16::from(max) + u16::from(min)).abs_diff(255)) };
+
+        (self.hue(), s, (f32::from(max) + f32::from(min)) / 510.0)
+    }
+
+    /// Converts `self` into the [HSV](https://en.wikipedia.org/wiki/HSL_and_HSV) color space, as `(hue, saturation, value)`.
+    ///
+    /// The hue is the same as [`Rgb::to_hsl`]'s, in degrees between `0.0` inclusive and `360.0` exclusive.
+    /// The saturation and value are between `0.0` and `1.0`; the value is `max(r, g, b) / 255`.
+    /// Grays have a saturation and a hue of `0.0`, and black is `(0.0, 0.0, 0.0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.to_hsv(), (0.0, 1.0, 1.0));
+    /// assert_eq!(Rgb::new(0, 0, 51).to_hsv(), (240.0, 1.0, 0.2));
+    /// assert_eq!(Rgb::BLACK.to_hsv(), (0.0, 0.0, 0.0));
+    /// ```
+    #[must_use]
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+
+        let s = if max == 0 { 0.0 } else { f32::from(max - min) / f32::from(max) };
+
+        (self.hue(), s, f32::from(max) / 255.0)
+    }
+
+    /// Returns the hue of `self` in degrees, between `0.0` inclusive and `360.0` exclusive, or `0.0` for grays.
+    #[allow(clippy::suboptimal_flops)]
+    fn hue(self) -> f32 {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+
+        if max == min {
+            return 0.0;
+        }
+
+        let (r, g, b) = (f32::from(self.r), f32::from(self.g), f32::from(self.b));
+        let c = f32::from(max - min);
+
+        let h = if max == self.r {
+            ((g - b) / c).rem_euclid(6.0)
+        }
+        else if max == self.g {
+            (b - r) / c + 2.0
+        }
+        else {
+            (r - g) / c + 4.0
+        };
+
+        h * 60.0
+    }
+
+    /// Returns the components of a color of hue `h` and chroma `c` whose smallest component is `m`, between `0.0` and `255.0`.
+    #[allow(clippy::suboptimal_flops)]
+    fn hue_components(h: f32, c: f32, m: f32) -> [f32; 3] {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+
+        let (r, g, b) = match h {
+            h if h < 1.0 => (c, x, 0.0),
+            h if h < 2.0 => (x, c, 0.0),
+            h if h < 3.0 => (0.0, c, x),
+            h if h < 4.0 => (0.0, x, c),
+            h if h < 5.0 => (x, 0.0, c),
+            _ => (c, 0.0, x)
+        };
+
+        [(r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0]
+    }
+
+    /// Converts a color from the HSL color space into floating-point components, between `0.0` and `255.0`.
+    #[allow(clippy::suboptimal_flops)]
+    fn hsl_components(h: f32, s: f32, l: f32) -> [f32; 3] {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+
+        Rgb::hue_components(h, c, l - c / 2.0)
+    }
+
+    /// Converts a color from the [HSL](https://en.wikipedia.org/wiki/HSL_and_HSV) color space, the inverse of [`Rgb::to_hsl`].
+    ///
+    /// The hue is in degrees, and wraps around; the saturation and lightness are clamped between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let (h, s, l) = Rgb::RED.to_hsl();
+    /// assert_eq!(Rgb::from_hsl(h, s, l), Rgb::RED);
+    ///
+    /// assert_eq!(Rgb::from_hsl(120.0, 1.0, 0.5), Rgb::GREEN);
+    /// assert_eq!(Rgb::from_hsl(-240.0, 1.0, 0.5), Rgb::GREEN);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Rgb {
+        let [r, g, b] = Rgb::hsl_components(h, s, l).map(|c| c.round().clamp(0.0, 255.0) as u8);
+
+        Rgb::new(r, g, b)
+    }
+
+    /// Converts a color from the [HSV](https://en.wikipedia.org/wiki/HSL_and_HSV) color space, the inverse of [`Rgb::to_hsv`].
+    ///
+    /// The hue is in degrees, and wraps around; the saturation and value are clamped between `0.0` and `1.0`.
+    /// A value of `0.0` is always black, regardless of the hue and saturation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let (h, s, v) = Rgb::MAGENTA.to_hsv();
+    /// assert_eq!(Rgb::from_hsv(h, s, v), Rgb::MAGENTA);
+    ///
+    /// assert_eq!(Rgb::from_hsv(60.0, 1.0, 1.0), Rgb::YELLOW);
+    /// assert_eq!(Rgb::from_hsv(123.0, 0.5, 0.0), Rgb::BLACK);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Rgb {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let c = v * s;
+
+        let [r, g, b] = Rgb::hue_components(h, c, v - c).map(|c| c.round().clamp(0.0, 255.0) as u8);
+
+        Rgb::new(r, g, b)
+    }
+
+    /// Changes the hue of `self` to `target_hue`, in degrees, while preserving its saturation and its [luma](Rgb::luma).
+    ///
+    /// The hue and saturation are those of the [HSL](https://en.wikipedia.org/wiki/HSL_and_HSV) color space;
+    /// the lightness is then adjusted so that the shading of `self` is kept, e.g. when recoloring a texture.
+    /// As some hues are brighter than others, very dark or very bright colors may be desaturated to reach the luma.
+    /// Grays are returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let red = Rgb::new(200, 40, 40);
+    /// let green = red.recolor_to_hue(120.0);
+    ///
+    /// assert!(green.g > green.r && green.g > green.b);
+    /// assert!(green.luma().abs_diff(red.luma()) <= 1);
+    ///
+    /// assert_eq!(Rgb::gray(100).recolor_to_hue(240.0), Rgb::gray(100));
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn recolor_to_hue(self, target_hue: f32) -> Rgb {
+        let luma = |[r, g, b]: [f32; 3]| 0.299 * r + 0.587 * g + 0.114 * b;
+
+        let (_, s, _) = self.to_hsl();
+        let target = luma([f32::from(self.r), f32::from(self.g), f32::from(self.b)]);
+
+        // the luma is monotonic with respect to the lightness
+        let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+        for _ in 0..24 {
+            let mid = (lo + hi) / 2.0;
+
+            if luma(Rgb::hsl_components(target_hue, s, mid)) < target {
+                lo = mid;
+            }
+            else {
+                hi = mid;
+            }
+        }
+
+        Rgb::from_hsl(target_hue, s, (lo + hi) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hsl_round_trip() {
+        for color in [Rgb::BLACK, Rgb::WHITE, Rgb::RED, Rgb::CYAN, Rgb::new(91, 206, 250), Rgb::new(245, 169, 184), Rgb::gray(128)] {
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Rgb::from_hsl(h, s, l), color);
+        }
+
+        for r in (0..=u8::MAX).step_by(5) {
+            for g in (0..=u8::MAX).step_by(5) {
+                for b in (0..=u8::MAX).step_by(5) {
+                    let color = Rgb::new(r, g, b);
+                    let (h, s, l) = color.to_hsl();
+
+                    assert!((0.0..360.0).contains(&h), "{color:?}");
+                    assert!((0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&l), "{color:?}");
+
+                    let round_trip = Rgb::from_hsl(h, s, l);
+                    assert!(round_trip.r.abs_diff(r) <= 1 && round_trip.g.abs_diff(g) <= 1 && round_trip.b.abs_diff(b) <= 1, "{color:?}");
+                }
+            }
+        }
+
+        assert_eq!(Rgb::gray(128).to_hsl(), (0.0, 0.0, 128.0 / 255.0));
+        assert_eq!(Rgb::from_hsl(360.0 + 240.0, 1.0, 0.5), Rgb::BLUE);
+    }
+
+    #[test]
+    fn recolor_keeps_luma() {
+        for color in [Rgb::new(200, 40, 40), Rgb::new(91, 206, 250), Rgb::new(30, 60, 20), Rgb::new(240, 230, 200), Rgb::gray(77)] {
+            for hue in [0.0, 45.0, 120.0, 200.0, 300.0, -60.0, 720.0] {
+                let recolored = color.recolor_to_hue(hue);
+
+                assert!(recolored.luma().abs_diff(color.luma()) <= 1, "{color:?} -> {recolored:?} ({hue})");
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        for dye in crate::Dye::VALUES {
+            let (h, s, v) = dye.color().to_hsv();
+            assert_eq!(Rgb::from_hsv(h, s, v), dye.color(), "{dye:?}");
+        }
+
+        for color in [Rgb::BLACK, Rgb::WHITE, Rgb::gray(77), Rgb::new(91, 206, 250)] {
+            let (h, s, v) = color.to_hsv();
+
+            assert!((h - color.to_hsl().0).abs() < f32::EPSILON);
+            assert_eq!(Rgb::from_hsv(h, s, v), color);
+        }
+    }
+}
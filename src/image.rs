@@ -0,0 +1,130 @@
+use ::image::RgbImage;
+use font8x8::legacy::{BASIC_LEGACY, LATIN_LEGACY};
+
+use crate::{Dye, FluentBundle, Rgb};
+
+/// The height of the label drawn below each swatch, in pixels.
+const LABEL_HEIGHT: u32 = 12;
+
+/// The glyph drawn for the characters not supported by the bitmap font.
+const MISSING_GLYPH: [u8; 8] = [0x00, 0x7E, 0x42, 0x42, 0x42, 0x42, 0x7E, 0x00];
+
+/// Renders a palette of `dyes`, each drawn as a square swatch of `cell` pixels with its localized color name below it.
+///
+/// The swatches are laid out in rows of `cols` columns, from left to right and from top to bottom.
+/// Each label is a 12-pixel high band of the dye's color, below its swatch, in which the name is drawn centered
+/// in the [readable text color](Rgb::readable_text_color) of the dye; the names wider than `cell` are clipped.
+/// The resulting image is thus `cols * cell` pixels wide and `rows * (cell + 12)` pixels high.
+///
+/// The names are drawn with an 8×8 bitmap font, which only supports the Latin-1 characters:
+/// the other characters, including all the Japanese ones, are drawn as empty boxes.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, Lang, render_labeled_palette};
+///
+/// let dyes = [Dye::SnowWhite, Dye::SkyBlue, Dye::InkBlue, Dye::SootBlack, Dye::DalamudRed];
+/// let image = render_labeled_palette(&dyes, &Lang::English.into_bundle(), 4, 64);
+///
+/// assert_eq!(image.dimensions(), (4 * 64, 2 * (64 + 12)));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn render_labeled_palette(dyes: &[Dye], bundle: &FluentBundle, cols: usize, cell: u32) -> RgbImage {
+    if dyes.is_empty() || cols == 0 {
+        return RgbImage::new(0, 0);
+    }
+
+    let rows = (dyes.len() + cols - 1) / cols;
+    let mut image = RgbImage::new(cols as u32 * cell, rows as u32 * (cell + LABEL_HEIGHT));
+
+    for (i, dye) in dyes.iter().enumerate() {
+        let x = (i % cols) as u32 * cell;
+        let y = (i / cols) as u32 * (cell + LABEL_HEIGHT);
+        let color = dye.color();
+
+        for py in y..y + cell + LABEL_HEIGHT {
+            for px in x..x + cell {
+                image.put_pixel(px, py, ::image::Rgb([color.r, color.g, color.b]));
+            }
+        }
+
+        draw_label(&mut image, dye.color_name(bundle), (x, y + cell + (LABEL_HEIGHT - 8) / 2), cell, color.readable_text_color());
+    }
+
+    image
+}
+
+/// Returns the 8×8 glyph of `c`, one byte per row, the least significant bit being the leftmost pixel.
+fn glyph(c: char) -> [u8; 8] {
+    match c {
+        ' '..='~' => BASIC_LEGACY[c as usize],
+        '\u{A0}'..='\u{FF}' => LATIN_LEGACY[c as usize - 0xA0],
+        '’' => BASIC_LEGACY[usize::from(b'\'')],
+        _ => MISSING_GLYPH
+    }
+}
+
+/// Draws `text` horizontally centered in a box of `width` pixels starting at `origin`, clipping the overflowing pixels.
+#[allow(clippy::cast_possible_truncation)]
+fn draw_label(image: &mut RgbImage, text: &str, origin: (u32, u32), width: u32, color: Rgb) {
+    let glyphs: Vec<[u8; 8]> = text.chars().map(glyph).collect();
+    let left = origin.0 + width.saturating_sub(glyphs.len() as u32 * 8) / 2;
+
+    for (i, glyph) in glyphs.iter().enumerate() {
+        for (dy, row) in (0..).zip(glyph) {
+            for dx in 0..8 {
+                let x = left + i as u32 * 8 + dx;
+
+                if row & (1 << dx) != 0 && x < origin.0 + width {
+                    image.put_pixel(x, origin.1 + dy, ::image::Rgb([color.r, color.g, color.b]));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Lang;
+
+    use super::*;
+
+    #[test]
+    fn dimensions() {
+        let bundle = Lang::English.into_bundle();
+        let dyes = [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::DalamudRed, Dye::AppleGreen];
+
+        let image = render_labeled_palette(&dyes, &bundle, 2, 20);
+        assert_eq!(image.dimensions(), (2 * 20, 3 * (20 + LABEL_HEIGHT)));
+
+        assert_eq!(render_labeled_palette(&dyes, &bundle, 5, 20).dimensions(), (5 * 20, 20 + LABEL_HEIGHT));
+        assert_eq!(render_labeled_palette(&dyes, &bundle, 0, 20).dimensions(), (0, 0));
+        assert_eq!(render_labeled_palette(&[], &bundle, 2, 20).dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn labels() {
+        let bundle = Lang::English.into_bundle();
+        let cell = 100;
+
+        for (i, dye) in [Dye::SnowWhite, Dye::SootBlack].into_iter().enumerate() {
+            let image = render_labeled_palette(&[dye], &bundle, 1, cell);
+            let (color, text) = (dye.color(), dye.color().readable_text_color());
+
+            let count = |y: std::ops::Range<u32>, c: Rgb| image.enumerate_pixels()
+                .filter(|(_, py, p)| y.contains(py) && p.0 == [c.r, c.g, c.b])
+                .count();
+
+            // the swatch is uniform, and the label is written below it
+            assert_eq!(count(0..cell, color), (cell * cell) as usize, "{i}");
+            assert!(count(cell..cell + LABEL_HEIGHT, text) > 0, "{i}");
+            assert!(count(cell..cell + LABEL_HEIGHT, color) > 0, "{i}");
+        }
+
+        assert_eq!(glyph('é'), LATIN_LEGACY[0xE9 - 0xA0]);
+        assert_eq!(glyph('ス'), MISSING_GLYPH);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Dye, Rgb};
+
+/// A [k-d tree](https://en.wikipedia.org/wiki/K-d_tree) of all the dyes, for fast repeated nearest dye searches.
+///
+/// Always returns the same dye as `Dye::try_from(color).unwrap_or_else(identity)`, but only computes a few distances
+/// per search instead of eighty-five. Build it once, and reuse it for every query, e.g. for every pixel of an image.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, DyeIndex, Rgb};
+///
+/// let index = DyeIndex::new();
+///
+/// assert_eq!(index.nearest(Rgb::new(155, 179, 98)), Dye::AppleGreen);
+/// assert_eq!(index.nearest(Rgb::WHITE), Dye::LotusPink);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DyeIndex {
+    /// The dyes of a balanced tree, flattened; the root of each subtree is at the middle of its slice,
+    /// and splits its remaining dyes by the component of its depth.
+    nodes: [Dye; Dye::COUNT]
+}
+
+impl DyeIndex {
+    /// Builds a new index over all eighty-five dyes.
+    #[must_use]
+    pub fn new() -> DyeIndex {
+        fn build(nodes: &mut [Dye], depth: usize) {
+            if nodes.len() > 1 {
+                let mid = nodes.len() / 2;
+                nodes.sort_unstable_by_key(|dye| (component(dye.color(), depth), *dye as u8));
+
+                let (left, right) = nodes.split_at_mut(mid);
+                build(left, depth + 1);
+                build(&mut right[1..], depth + 1);
+            }
+        }
+
+        let mut nodes = Dye::VALUES;
+        build(&mut nodes, 0);
+
+        DyeIndex { nodes }
+    }
+
+    /// Returns the closest [`Dye`] to `color`.
+    ///
+    /// Ties are broken like [`Dye::try_from`], in favor of the first dye in [`Dye::VALUES`].
+    #[must_use]
+    pub fn nearest(&self, color: Rgb) -> Dye {
+        /// Updates `best` with the closest dye of `nodes`, as a `(distance, dye)` pair.
+        fn search(nodes: &[Dye], depth: usize, color: Rgb, best: &mut (u32, Dye)) {
+            if nodes.is_empty() {
+                return;
+            }
+
+            let mid = nodes.len() / 2;
+            let dye = nodes[mid];
+
+            let distance = dye.color().distance(color);
+            if (distance, dye as u8) < (best.0, best.1 as u8) {
+                *best = (distance, dye);
+            }
+
+            let diff = i32::from(component(color, depth)) - i32::from(component(dye.color(), depth));
+            let (near, far) = if diff < 0 { (&nodes[..mid], &nodes[mid + 1..]) } else { (&nodes[mid + 1..], &nodes[..mid]) };
+
+            search(near, depth + 1, color, best);
+
+            // the other side can only contain a closer dye if it is within the splitting plane
+            if diff.unsigned_abs().pow(2) <= best.0 {
+                search(far, depth + 1, color, best);
+            }
+        }
+
+        let mut best = (u32::MAX, Dye::VALUES[0]);
+        search(&self.nodes, 0, color, &mut best);
+
+        best.1
+    }
+}
+
+impl Default for DyeIndex {
+    /// Builds a new index over all eighty-five dyes.
+    #[inline]
+    fn default() -> DyeIndex {
+        DyeIndex::new()
+    }
+}
+
+/// Returns the component of `color` used to split the nodes at `depth`.
+const fn component(color: Rgb, depth: usize) -> u8 {
+    match depth % 3 {
+        0 => color.r,
+        1 => color.g,
+        _ => color.b
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::identity;
+
+    use super::*;
+
+    #[test]
+    fn nearest_is_linear() {
+        let index = DyeIndex::new();
+
+        // xorshift32
+        let mut state = 0x2545_F491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let color = Rgb::from(next());
+            assert_eq!(index.nearest(color), Dye::try_from(color).unwrap_or_else(identity), "{color:?}");
+        }
+
+        for dye in Dye::VALUES {
+            assert_eq!(index.nearest(dye.color()), dye);
+        }
+
+        for color in [Rgb::BLACK, Rgb::WHITE, Rgb::RED, Rgb::GREEN, Rgb::BLUE] {
+            assert_eq!(index.nearest(color), Dye::try_from(color).unwrap_or_else(identity), "{color:?}");
+        }
+    }
+}
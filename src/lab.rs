@@ -0,0 +1,465 @@
+use crate::Rgb;
+
+/// Converts an sRGB component into linear light, between `0.0` and `1.0`.
+pub(crate) fn to_linear(c: u8) -> f32 {
+    let c = f32::from(c) / 255.0;
+
+    if c <= 0.040_45 {
+        c / 12.92
+    }
+    else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light component back into sRGB, clamping it to `0..=255`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::suboptimal_flops)]
+pub(crate) fn from_linear(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    }
+    else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+
+    (c * 255.0).round() as u8
+}
+
+impl Rgb {
+    /// Converts `self` into the [CIELAB](https://en.wikipedia.org/wiki/CIELAB_color_space) color space, using the D65 white point.
+    #[allow(clippy::suboptimal_flops)]
+    pub(crate) fn to_lab(self) -> [f32; 3] {
+        fn f(t: f32) -> f32 {
+            const DELTA: f32 = 6.0 / 29.0;
+
+            if t > DELTA * DELTA * DELTA {
+                t.cbrt()
+            }
+            else {
+                t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+            }
+        }
+
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+
+        let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+        let y =  0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b;
+        let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+
+    /// Converts `self` into the [OKLab](https://bottosson.github.io/posts/oklab/) color space, as `(lightness, a, b)`.
+    ///
+    /// OKLab is a perceptually uniform color space: the lightness is between `0.0` for black and `1.0` for white,
+    /// and `a` and `b` are roughly between `-0.4` and `0.4`. Blending colors in this space, as [`Rgb::lerp_oklab`] does,
+    /// gives smoother gradients than blending their sRGB components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let (l, a, b) = Rgb::WHITE.to_oklab();
+    /// assert!((l - 1.0).abs() < 1e-4 && a.abs() < 1e-4 && b.abs() < 1e-4);
+    ///
+    /// assert_eq!(Rgb::BLACK.to_oklab(), (0.0, 0.0, 0.0));
+    /// assert_eq!(Rgb::from_oklab(Rgb::RED.to_oklab()), Rgb::RED);
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn to_oklab(self) -> (f32, f32, f32) {
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+        (
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s
+        )
+    }
+
+    /// Converts a color of the [OKLab](https://bottosson.github.io/posts/oklab/) color space, as `(lightness, a, b)`, back into sRGB.
+    ///
+    /// Colors outside of the sRGB gamut are clamped component-wise. This is the inverse of [`Rgb::to_oklab`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from_oklab((1.0, 0.0, 0.0)), Rgb::WHITE);
+    /// assert_eq!(Rgb::from_oklab(Rgb::new(91, 206, 250).to_oklab()), Rgb::new(91, 206, 250));
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn from_oklab((l, a, b): (f32, f32, f32)) -> Rgb {
+        let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+        Rgb {
+            r: from_linear(4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s),
+            g: from_linear(-1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s),
+            b: from_linear(-0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s)
+        }
+    }
+
+    /// Converts `self` into the OKLCh color space, the cylindrical form of [OKLab](Rgb::to_oklab),
+    /// as `(lightness, chroma, hue)`.
+    ///
+    /// The lightness is the same as OKLab's; the chroma is the distance to the gray axis, and the hue is in degrees,
+    /// between `0.0` inclusive and `360.0` exclusive. Grays have a hue of `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let (_, c, h) = Rgb::gray(128).to_oklch();
+    /// assert!(c < 1e-4);
+    /// assert_eq!(h, 0.0);
+    ///
+    /// let (_, _, red) = Rgb::RED.to_oklch();
+    /// let (_, _, blue) = Rgb::BLUE.to_oklch();
+    /// assert!(red < 90.0 && (180.0..360.0).contains(&blue));
+    /// ```
+    #[must_use]
+    pub fn to_oklch(self) -> (f32, f32, f32) {
+        let (l, a, b) = self.to_oklab();
+        let c = a.hypot(b);
+
+        // grays have an arbitrary hue, made of rounding errors
+        (l, c, if c < 1e-4 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) })
+    }
+
+    /// Converts a color of the OKLCh color space, as `(lightness, chroma, hue)`, back into sRGB.
+    ///
+    /// The hue is in degrees, and may be outside of `0.0..360.0`. Colors outside of the sRGB gamut are clamped component-wise.
+    /// This is the inverse of [`Rgb::to_oklch`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from_oklch(Rgb::BLUE.to_oklch()), Rgb::BLUE);
+    ///
+    /// let (l, c, h) = Rgb::RED.to_oklch();
+    /// assert_eq!(Rgb::from_oklch((l, c, h + 360.0)), Rgb::RED);
+    /// ```
+    #[must_use]
+    pub fn from_oklch((l, c, h): (f32, f32, f32)) -> Rgb {
+        let (sin, cos) = h.to_radians().sin_cos();
+
+        Rgb::from_oklab((l, c * cos, c * sin))
+    }
+
+    /// Returns the color between `self` and `other` at `t`, interpolated in the [OKLab](Rgb::to_oklab) color space.
+    ///
+    /// Unlike [`Rgb::lerp`], the intermediate colors are perceptually evenly spaced, and don't dip in lightness
+    /// between complementary colors. `t` is clamped between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.lerp_oklab(Rgb::BLUE, 0.0), Rgb::RED);
+    /// assert_eq!(Rgb::RED.lerp_oklab(Rgb::BLUE, 1.0), Rgb::BLUE);
+    ///
+    /// // the sRGB midpoint between yellow and blue is gray, the OKLab one isn't
+    /// assert_eq!(Rgb::YELLOW.lerp(Rgb::BLUE, 0.5), Rgb::gray(128));
+    /// assert_ne!(Rgb::YELLOW.lerp_oklab(Rgb::BLUE, 0.5), Rgb::gray(128));
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn lerp_oklab(self, other: Rgb, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let ((l1, a1, b1), (l2, a2, b2)) = (self.to_oklab(), other.to_oklab());
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        Rgb::from_oklab((lerp(l1, l2), lerp(a1, a2), lerp(b1, b2)))
+    }
+
+    /// Returns the Euclidean distance between `self` and `other` in the [OKLab](Rgb::to_oklab) color space,
+    /// from `0.0` for identical colors to about `1.0` for black and white.
+    ///
+    /// Like [`Rgb::delta_e`], this takes human perception into account, but is much cheaper to compute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.delta_e_ok(Rgb::RED), 0.0);
+    /// assert!((Rgb::BLACK.delta_e_ok(Rgb::WHITE) - 1.0).abs() < 1e-4);
+    ///
+    /// // two pairs of colors with the same Euclidean distance are not perceived as equally different
+    /// let (greens, blues) = ((Rgb::new(0, 200, 0), Rgb::new(0, 230, 0)), (Rgb::new(0, 0, 200), Rgb::new(0, 0, 230)));
+    ///
+    /// assert_eq!(greens.0.distance(greens.1), blues.0.distance(blues.1));
+    /// assert!(greens.0.delta_e_ok(greens.1) != blues.0.delta_e_ok(blues.1));
+    /// ```
+    #[must_use]
+    pub fn delta_e_ok(self, other: Rgb) -> f32 {
+        let (l1, a1, b1) = self.to_oklab();
+        let (l2, a2, b2) = other.to_oklab();
+
+        (l1 - l2).hypot(a1 - a2).hypot(b1 - b2)
+    }
+
+    /// Returns the [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance) of `self`,
+    /// between `0.0` for black and `1.0` for white.
+    ///
+    /// The components are linearized from sRGB before being weighted, as specified by WCAG.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::BLACK.relative_luminance(), 0.0);
+    /// assert!((Rgb::WHITE.relative_luminance() - 1.0).abs() < 1e-6);
+    ///
+    /// assert!(Rgb::GREEN.relative_luminance() > Rgb::RED.relative_luminance());
+    /// assert!(Rgb::RED.relative_luminance() > Rgb::BLUE.relative_luminance());
+    /// ```
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn relative_luminance(self) -> f32 {
+        0.2126 * to_linear(self.r) + 0.7152 * to_linear(self.g) + 0.0722 * to_linear(self.b)
+    }
+
+    /// Returns the [WCAG contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between `self` and `other`,
+    /// from `1.0` for identical colors to `21.0` for black and white.
+    ///
+    /// WCAG requires a ratio of at least 4.5:1 between normal text and its background (3:1 for large text),
+    /// and 7:1 for its enhanced level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert!((Rgb::BLACK.contrast_ratio(Rgb::WHITE) - 21.0).abs() < 1e-4);
+    /// assert_eq!(Rgb::RED.contrast_ratio(Rgb::RED), 1.0);
+    ///
+    /// assert_eq!(Rgb::RED.contrast_ratio(Rgb::BLACK), Rgb::BLACK.contrast_ratio(Rgb::RED));
+    /// assert!(Rgb::BLUE.contrast_ratio(Rgb::WHITE) >= 4.5);
+    /// ```
+    #[must_use]
+    pub fn contrast_ratio(self, other: Rgb) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Returns either black or white, whichever has the highest [contrast ratio](Rgb::contrast_ratio)
+    /// against `self`, for text displayed over a background of color `self`.
+    ///
+    /// The returned color always has a contrast ratio of at least 4.5:1, as required by WCAG for normal text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::WHITE.readable_text_color(), Rgb::BLACK);
+    /// assert_eq!(Rgb::BLACK.readable_text_color(), Rgb::WHITE);
+    ///
+    /// assert_eq!(Rgb::RED.readable_text_color(), Rgb::BLACK);
+    /// assert_eq!(Rgb::BLUE.readable_text_color(), Rgb::WHITE);
+    /// ```
+    #[must_use]
+    pub fn readable_text_color(self) -> Rgb {
+        if self.contrast_ratio(Rgb::WHITE) > self.contrast_ratio(Rgb::BLACK) {
+            Rgb::WHITE
+        }
+        else {
+            Rgb::BLACK
+        }
+    }
+
+    /// Computes the [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000) color difference between `self` and `other`.
+    ///
+    /// Unlike [`Rgb::distance`], this takes human perception into account: both colors are converted into the CIELAB
+    /// color space, where the difference is corrected for lightness, chroma and hue. A difference of `1.0` is roughly
+    /// the smallest one noticeable by the human eye, and `100.0` is the difference between black and white.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.delta_e(Rgb::RED), 0.0);
+    /// assert!((Rgb::BLACK.delta_e(Rgb::WHITE) - 100.0).abs() < 0.01);
+    ///
+    /// // two pairs of colors with the same Euclidean distance are not perceived as equally different
+    /// let (greens, blues) = ((Rgb::new(0, 200, 0), Rgb::new(0, 230, 0)), (Rgb::new(0, 0, 200), Rgb::new(0, 0, 230)));
+    ///
+    /// assert_eq!(greens.0.distance(greens.1), blues.0.distance(blues.1));
+    /// assert!(greens.0.delta_e(greens.1) > blues.0.delta_e(blues.1));
+    /// ```
+    #[must_use]
+    pub fn delta_e(self, other: Rgb) -> f32 {
+        ciede2000(self.to_lab(), other.to_lab())
+    }
+}
+
+/// Computes the CIEDE2000 color difference between two CIELAB colors, following Sharma et al. (2005).
+#[allow(clippy::suboptimal_flops, clippy::similar_names)]
+fn ciede2000([l1, a1, b1]: [f32; 3], [l2, a2, b2]: [f32; 3]) -> f32 {
+    /// `25^7`
+    const POW25_7: f32 = 6_103_515_625.0;
+
+    /// Returns the hue angle of `(a, b)` in degrees, between `0.0` and `360.0`.
+    fn hue(a: f32, b: f32) -> f32 {
+        if a == 0.0 && b == 0.0 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) }
+    }
+
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt());
+
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    }
+    else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    }
+    else if h2 > h1 {
+        h2 - h1 - 360.0
+    }
+    else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    }
+    else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    }
+    else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    }
+    else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0
+        - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+
+    let d_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt();
+    let sl = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_bar;
+    let sh = 1.0 + 0.015 * c_bar * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (dl, dc, dh) = (dl / sl, dc / sc, dh / sh);
+    (dl * dl + dc * dc + dh * dh + rt * dc * dh).max(0.0).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn readable_text_color() {
+        let contrast = Rgb::contrast_ratio;
+
+        // the previous heuristic picked white for all these mid-tones, as they are far enough from white
+        for bg in [Rgb::RED, Rgb::new(0, 160, 0), Rgb::new(230, 100, 0), Rgb::MAGENTA] {
+            assert!(bg.distance(Rgb::WHITE) >= Rgb::gray(127).distance(Rgb::WHITE));
+            assert_eq!(bg.readable_text_color(), Rgb::BLACK, "{bg:?}");
+            assert!(contrast(bg, Rgb::BLACK) > contrast(bg, Rgb::WHITE), "{bg:?}");
+        }
+
+        for bg in [Rgb::BLUE, Rgb::gray(100), Rgb::new(120, 40, 160)] {
+            assert_eq!(bg.readable_text_color(), Rgb::WHITE, "{bg:?}");
+        }
+
+        for c in 0..=u8::MAX {
+            let bg = Rgb::new(c, u8::MAX - c, c / 2);
+            let fg = bg.readable_text_color();
+            let other = if fg == Rgb::WHITE { Rgb::BLACK } else { Rgb::WHITE };
+
+            assert!(contrast(bg, fg) >= contrast(bg, other), "{bg:?}");
+            assert!(contrast(bg, fg) >= 4.5, "{bg:?}");
+        }
+    }
+
+    #[test]
+    fn ciede2000_sharma() {
+        // test data from Sharma et al. (2005)
+        for (lab1, lab2, expected) in [
+            ([50.0, 2.677_2, -79.775_1], [50.0, 0.0, -82.748_5], 2.042_5),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.366_9),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.149_2),
+            ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.897_7),
+            ([60.257_4, -34.009_9, 36.267_7], [60.462_6, -34.175_1, 39.438_7], 1.264_4)
+        ] {
+            let actual = ciede2000(lab1, lab2);
+            assert!((actual - expected).abs() < 1e-3, "{lab1:?} {lab2:?}: {actual} != {expected}");
+            assert!((ciede2000(lab2, lab1) - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn oklab_round_trip() {
+        for c in (0..=u8::MAX).step_by(5) {
+            for color in [Rgb::gray(c), Rgb::new(c, u8::MAX - c, c / 2), Rgb::new(c / 3, c, u8::MAX - c / 4)] {
+                let (l, a, b) = color.to_oklab();
+
+                assert!((0.0..=1.0 + 1e-4).contains(&l), "{color:?}");
+                assert!(a.abs() < 0.5 && b.abs() < 0.5, "{color:?}");
+
+                assert_eq!(Rgb::from_oklab(color.to_oklab()), color);
+                assert_eq!(Rgb::from_oklch(color.to_oklch()), color);
+
+                let (l2, c, h) = color.to_oklch();
+                assert!((l - l2).abs() < 1e-6 && (c - a.hypot(b)).abs() < 1e-6, "{color:?}");
+                assert!((0.0..360.0).contains(&h), "{color:?}");
+            }
+        }
+
+        // the reference values of Ottosson
+        let (l, a, b) = Rgb::RED.to_oklab();
+        assert!((l - 0.627_955).abs() < 1e-3 && (a - 0.224_863).abs() < 1e-3 && (b - 0.125_846).abs() < 1e-3);
+    }
+
+    #[test]
+    fn oklab_gradient() {
+        let (from, to) = (Rgb::YELLOW, Rgb::BLUE);
+        // the OKLab gradient differs from the sRGB one, and is perceptually more even
+        assert!((1..10_u8).map(|i| f32::from(i) / 10.0).any(|t| from.lerp(to, t) != from.lerp_oklab(to, t)));
+
+        let spread = |lerp: fn(Rgb, Rgb, f32) -> Rgb| {
+            let deltas: Vec<f32> = (0..10_u8).map(|i| lerp(from, to, f32::from(i) / 10.0).delta_e_ok(lerp(from, to, f32::from(i + 1) / 10.0))).collect();
+            deltas.iter().copied().fold(f32::MIN, f32::max) - deltas.iter().copied().fold(f32::MAX, f32::min)
+        };
+
+        assert!(spread(Rgb::lerp_oklab) < spread(Rgb::lerp));
+    }
+}
@@ -10,6 +10,23 @@
 //!
 //! These two features are enabled by default.
 //!
+//! - `simd`: enables a vectorized nearest dye search, `Dye::nearest_simd`.
+//!
+//! - `clap`: implements [`clap::ValueEnum`](https://docs.rs/clap/latest/clap/trait.ValueEnum.html) for `Dye`,
+//! using the dyes' short names.
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for `Rgb` (as a `#rrggbb` string),
+//! `Dye` and `Snack` (as their short names), and `SnackList` (as a map from snacks to their counts).
+//!
+//! - `egui`: implements `From<Rgb>` and `From<Dye>` for [`egui::Color32`](https://docs.rs/egui/latest/egui/struct.Color32.html).
+//! Unlike the rest of the crate, this feature requires the same Rust version as egui.
+//!
+//! - `ratatui`: implements `From<Rgb>` and `From<Dye>` for [`ratatui::style::Color`](https://docs.rs/ratatui/latest/ratatui/style/enum.Color.html),
+//! as its `Rgb` variant. Unlike the rest of the crate, this feature requires the same Rust version as ratatui.
+//!
+//! - `image`: enables rendering palettes of dyes into [`image::RgbImage`](https://docs.rs/image/latest/image/type.RgbImage.html)s.
+//! Enables the `fluent` feature, and requires the same Rust version as image.
+//!
 //! # Examples
 //!
 //! To print all the dyes:
@@ -21,10 +38,7 @@ use chocodye::{Dye, Lang};
 
 let bundle = Lang::English.into_bundle();
 
-let mut dyes = Dye::VALUES;
-dyes.sort_unstable_by_key(|dye| 255 - dye.luma());
-
-for dye in dyes {
+for dye in Dye::sorted_by_luma() {
     print!("{} ", dye.ansi_color_name(&bundle));
 }
 
@@ -36,10 +50,7 @@ println!();
 ```
 use chocodye::Dye;
 
-let mut dyes = Dye::VALUES;
-dyes.sort_unstable_by_key(|dye| 255 - dye.luma());
-
-println!("{:#?}", dyes);
+println!("{:#?}", Dye::sorted_by_luma());
 ```
 "#)]
 
@@ -89,30 +100,63 @@ for category in Category::VALUES {
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::{array, fmt};
+use std::{array, fmt, iter};
+use std::iter::FusedIterator;
+#[cfg(feature = "fluent")]
+use std::io;
+use std::collections::HashSet;
+use std::error::Error;
 use std::convert::identity;
 use std::fmt::Formatter;
 use std::num::NonZeroU64;
 
-pub use dye::{Category, Dye};
+pub use css::ParseCssColorError;
+pub use cvd::CvdKind;
+pub use dye::{Category, Dye, ParseDyeError};
+pub use index::DyeIndex;
+pub use palette::{gradient_stops, median_cut, unique_dyes};
 pub use rgb::{ParseHexError, Rgb};
+pub use rgba::Rgba;
 pub use snack::Snack;
 
 #[cfg(feature = "fluent")]
-pub use crate::fluent::{FluentBundle, Lang, ParseLangError};
+pub use crate::fluent::{all_translations, FluentBundle, Lang, message_keys, ParseLangError};
+
+#[cfg(feature = "image")]
+pub use crate::image::render_labeled_palette;
+
 #[cfg(feature = "fluent")]
 #[doc(hidden)]
 pub use crate::fluent::__format_message;
 #[cfg(feature = "truecolor")]
-pub use crate::truecolor::ansi_text;
+pub use crate::truecolor::{ansi_text, ansi_text_16};
 
 #[cfg(feature = "fluent")]
 mod fluent;
 
+mod css;
+mod cvd;
 mod dye;
+mod hsl;
+mod index;
+mod lab;
+mod palette;
 mod rgb;
+mod rgba;
 mod snack;
 
+#[cfg(feature = "egui")]
+mod egui;
+
+#[cfg(feature = "image")]
+mod image;
+
+#[cfg(feature = "ratatui")]
+mod ratatui;
+
+#[cfg(feature = "serde")]
+mod serde;
+
 #[cfg(feature = "truecolor")]
 mod truecolor;
 
@@ -134,12 +178,352 @@ mod truecolor;
 /// assert_eq!(make_meal(Dye::SalmonPink, Dye::RosePink), [ Snack::Fruit,  Snack::Berries]);
 /// assert_eq!(make_meal(Dye::RosePink, Dye::SalmonPink), [-Snack::Fruit, -Snack::Berries]);
 /// ```
+#[must_use]
 pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
+    greedy_meal(starting_dye.color(), final_dye.color(), Some(final_dye), false, 2).0
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but favoring the kinds of snack already eaten.
+///
+/// Whenever several snacks would bring the chocobo equally close to the desired dye, the ones that were already
+/// fed are preferred. The resulting meal may be longer than the one returned by [`make_meal`], but may require
+/// fewer distinct fruits to be bought. As the search is greedy, this is not guaranteed.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_meal_few_kinds, SnackList};
+///
+/// let meal = make_meal(Dye::GoobbueGrey, Dye::DesertYellow);
+/// let few_kinds = make_meal_few_kinds(Dye::GoobbueGrey, Dye::DesertYellow);
+///
+/// assert_eq!(SnackList::from(meal.as_slice()).kinds(), 4);
+/// assert_eq!(SnackList::from(few_kinds.as_slice()).kinds(), 2);
+/// ```
+#[must_use]
+pub fn make_meal_few_kinds(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
+    greedy_meal(starting_dye.color(), final_dye.color(), Some(final_dye), true, 2).0
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but stops as soon as the chocobo's color is within a
+/// [squared distance](Rgb::distance) of `tolerance` from the final dye.
+///
+/// The returned meal is a prefix of the one returned by [`make_meal`], and is never longer.
+/// Since the final dye is not necessarily reached, the resulting dye may be one of its neighbors;
+/// a tolerance of `0` behaves like [`make_meal`].
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_meal_within};
+///
+/// let meal = make_meal(Dye::SnowWhite, Dye::SootBlack);
+/// let close_enough = make_meal_within(Dye::SnowWhite, Dye::SootBlack, 1000);
+///
+/// assert!(close_enough.len() < meal.len());
+/// assert!(meal.starts_with(&close_enough));
+/// ```
+#[must_use]
+pub fn make_meal_within(starting_dye: Dye, final_dye: Dye, tolerance: u32) -> Vec<Snack> {
+    let mut meal = make_meal(starting_dye, final_dye);
+
+    let final_color = final_dye.color();
+    let mut current_color = starting_dye.color();
+
+    let len = meal.iter().take_while(|snack| {
+        let done = current_color.distance(final_color) <= tolerance;
+        current_color = snack.alter(current_color).unwrap_or(current_color);
+
+        !done
+    }).count();
+
+    meal.truncate(len);
+    meal
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but towards any of several acceptable final dyes.
+///
+/// Returns the final dye whose meal has the fewest snacks, along with that meal; ties are resolved in favor of
+/// the first dye of `final_dyes`. Returns `None` if `final_dyes` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_meal_any};
+///
+/// let blues = [Dye::InkBlue, Dye::SkyBlue, Dye::CeruleumBlue];
+/// let (dye, meal) = make_meal_any(Dye::DesertYellow, &blues).unwrap();
+///
+/// assert_eq!(meal, make_meal(Dye::DesertYellow, dye));
+/// assert!(blues.iter().all(|blue| make_meal(Dye::DesertYellow, *blue).len() >= meal.len()));
+///
+/// assert_eq!(make_meal_any(Dye::DesertYellow, &[]), None);
+/// ```
+#[must_use]
+pub fn make_meal_any(starting_dye: Dye, final_dyes: &[Dye]) -> Option<(Dye, Vec<Snack>)> {
+    final_dyes.iter()
+        .map(|final_dye| (*final_dye, make_meal(starting_dye, *final_dye)))
+        .reduce(|best, candidate| if candidate.1.len() < best.1.len() { candidate } else { best })
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but starting from an arbitrary color instead of a dye,
+/// e.g. a chocobo that has not finished eating.
+///
+/// The snacks only move the color on a lattice; if `final_dye` can't be reached from `starting_color`,
+/// the returned meal is the best approach, bringing the chocobo as close as possible to `final_dye`.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_meal_from_color, Rgb};
+///
+/// assert_eq!(make_meal_from_color(Dye::SalmonPink.color(), Dye::RosePink), make_meal(Dye::SalmonPink, Dye::RosePink));
+///
+/// let meal = make_meal_from_color(Rgb::new(230, 220, 211), Dye::SootBlack);
+/// let reached = meal.iter().try_fold(Rgb::new(230, 220, 211), |color, snack| snack.alter(color)).unwrap();
+///
+/// assert_eq!(Dye::try_from(reached).unwrap_or_else(|dye| dye), Dye::SootBlack);
+/// ```
+#[must_use]
+pub fn make_meal_from_color(starting_color: Rgb, final_dye: Dye) -> Vec<Snack> {
+    greedy_meal(starting_color, final_dye.color(), Some(final_dye), false, 2).0
+}
+
+/// Returns the additional snacks needed to reach `final_dye`, after a chocobo whose plumage was `starting_dye`
+/// has already been fed `already_fed`, or `None` if any color component had overflowed while eating `already_fed`.
+///
+/// This is useful when the desired dye changes mid-meal: the snacks already eaten are kept, and the plan resumes
+/// from the current color with [`make_meal_from_color`].
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, replan, Snack};
+///
+/// let meal = make_meal(Dye::SnowWhite, Dye::SootBlack);
+/// let (fed, _) = meal.split_at(20);
+///
+/// let rest = replan(fed, Dye::SnowWhite, Dye::InkBlue).unwrap();
+/// let resulting_dye = chocodye::resulting_dye(Dye::SnowWhite, &[fed, &rest].concat());
+///
+/// assert_eq!(resulting_dye.unwrap_or_else(|dye| dye), Dye::InkBlue);
+///
+/// assert_eq!(replan(&[Snack::Apple], Dye::LotusPink, Dye::InkBlue), None);
+/// ```
+#[must_use]
+pub fn replan(already_fed: &[Snack], starting_dye: Dye, final_dye: Dye) -> Option<Vec<Snack>> {
+    apply_meal(starting_dye, already_fed).map(|current_color| make_meal_from_color(current_color, final_dye))
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but targeting an arbitrary color instead of a dye.
+///
+/// Most colors can't be reached exactly; the search stops as soon as no snack can get any closer to `final_color`.
+/// The color actually reached is returned along with the meal, and is the closest color to `final_color` along the way.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal_to_color, Rgb};
+///
+/// let (meal, reached) = make_meal_to_color(Dye::SnowWhite, Rgb::new(230, 20, 20));
+///
+/// assert_eq!(reached, Rgb::new(228, 23, 18));
+/// assert!(reached.distance(Rgb::new(230, 20, 20)) < Dye::SnowWhite.color().distance(Rgb::new(230, 20, 20)));
+///
+/// assert_eq!(make_meal_to_color(Dye::InkBlue, Dye::InkBlue.color()), (Vec::new(), Dye::InkBlue.color()));
+/// ```
+#[must_use]
+pub fn make_meal_to_color(starting_dye: Dye, final_color: Rgb) -> (Vec<Snack>, Rgb) {
+    let (meal, reached_color, _) = greedy_meal(starting_dye.color(), final_color, None, false, 2);
+    (meal, reached_color)
+}
+
+/// Returns the distances between the chocobo's color and the final dye after each step of [`make_meal`].
+///
+/// A step is either a single snack, or two snacks when no single snack can get any closer to the final dye;
+/// there are therefore at most as many distances as there are snacks in the meal. The distances never increase.
+///
+/// This is a debugging aid for inspecting how a meal converges.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal_distances};
+///
+/// assert_eq!(make_meal_distances(Dye::SalmonPink, Dye::RosePink), [94, 9]);
+/// assert!(make_meal_distances(Dye::InkBlue, Dye::InkBlue).is_empty());
+/// ```
+#[must_use]
+pub fn make_meal_distances(starting_dye: Dye, final_dye: Dye) -> Vec<u32> {
+    greedy_meal(starting_dye.color(), final_dye.color(), Some(final_dye), false, 2).2
+}
+
+/// Creates a vector of [`Snack`] like [`make_meal`], but whose every intermediate color stays within a [`Category`].
+///
+/// The current implementation is a greedy search; at each step, it tries all single snacks and all pairs of snacks,
+/// discards those bringing the chocobo's color closer to a dye of another category, even temporarily, and takes the one that brings it
+/// the closest to the final dye. Returns `None` if no remaining possibility gets any closer, including when the
+/// starting or final dye is not in `category`.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Category, Dye, make_meal_in_category};
+///
+/// assert!(make_meal_in_category(Dye::AshGrey, Dye::GoobbueGrey, Category::White).is_some());
+///
+/// assert_eq!(make_meal_in_category(Dye::SnowWhite, Dye::InkBlue, Category::White), None);
+/// ```
+#[must_use]
+pub fn make_meal_in_category(starting_dye: Dye, final_dye: Dye, category: Category) -> Option<Vec<Snack>> {
+    if starting_dye.category() != category || final_dye.category() != category {
+        return None;
+    }
+
+    let final_color = final_dye.color();
+    let nearest = |color: Rgb| Dye::try_from(color).unwrap_or_else(identity);
+
     let mut meal = Vec::new();
+    let mut current_color = starting_dye.color();
+
+    while nearest(current_color) != final_dye {
+        let singles = Snack::VALUES.map(|snack| vec![snack]);
+        let pairs = Snack::VALUES.into_iter()
+            .flat_map(|s| Snack::VALUES.into_iter().filter(move |t| s as u8 <= *t as u8 && *t != -s).map(move |t| vec![s, t]));
+
+        let (snacks, next_color) = singles.into_iter().chain(pairs)
+            .filter_map(|snacks| {
+                snacks.iter()
+                    .try_fold(current_color, |color, snack| snack.alter(color).filter(|color| nearest(*color).category() == category))
+                    .map(|color| (snacks, color))
+            })
+            .filter(|(_, color)| color.distance(final_color) < current_color.distance(final_color))
+            .min_by_key(|(snacks, color)| (color.distance(final_color), snacks.len()))?;
+
+        meal.extend(snacks);
+        current_color = next_color;
+    }
+
+    Some(meal)
+}
+
+/// The highest lookahead depth accepted by [`make_meal_lookahead`].
+const MAX_LOOKAHEAD: usize = 4;
 
+/// Creates a vector of [`Snack`] like [`make_meal`], but looking up to `depth` snacks ahead.
+///
+/// [`make_meal`] tries a single snack, then two snacks if a single one can no longer get any closer to the final dye;
+/// a depth of `1` only tries single snacks, and may stop before reaching the final dye, while a depth of `2` returns
+/// the same meal as [`make_meal`]. `depth` is clamped between `1` and `4`.
+///
+/// From a depth of `3`, every combination of up to `depth` snacks is tried at each step, and the one getting the
+/// closest to the final dye per snack eaten is taken, so that a few snacks aren't wasted moving sideways; the search
+/// stops once the chocobo's color is closer to the final dye than to any other. The shortest meal found with a depth
+/// between `3` and `depth` is returned, or the meal of [`make_meal`] if none is shorter, so a deeper search never
+/// returns a longer meal. Deeper searches are slower, as there are up to 6<sup>*n*</sup> combinations of *n* snacks.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_meal_lookahead, Snack};
+///
+/// let meal = make_meal(Dye::OliveGreen, Dye::MudGreen);
+///
+/// assert_eq!(meal, [Snack::Apple, Snack::Pineapple, Snack::Fruit, Snack::Plum]);
+/// assert_eq!(make_meal_lookahead(Dye::OliveGreen, Dye::MudGreen, 2), meal);
+/// assert_eq!(make_meal_lookahead(Dye::OliveGreen, Dye::MudGreen, 3), [Snack::Fruit, Snack::Pineapple]);
+/// ```
+#[must_use]
+pub fn make_meal_lookahead(starting_dye: Dye, final_dye: Dye, depth: usize) -> Vec<Snack> {
+    let depth = depth.clamp(1, MAX_LOOKAHEAD);
+
+    if depth <= 2 {
+        return greedy_meal(starting_dye.color(), final_dye.color(), Some(final_dye), false, depth).0;
+    }
+
+    (3..=depth)
+        .filter_map(|depth| lookahead_meal(starting_dye, final_dye, depth))
+        .fold(make_meal(starting_dye, final_dye), |best, meal| if meal.len() < best.len() { meal } else { best })
+}
+
+/// The search behind [`make_meal_lookahead`] for a depth of at least `3`, or `None` if it gets stuck before
+/// reaching `final_dye`.
+fn lookahead_meal(starting_dye: Dye, final_dye: Dye, depth: usize) -> Option<Vec<Snack>> {
     let final_color = final_dye.color();
 
+    let mut meal = Vec::new();
     let mut current_color = starting_dye.color();
+
+    while Dye::try_from(current_color).unwrap_or_else(identity) != final_dye {
+        let current_distance = current_color.distance(final_color);
+
+        // `a` makes more progress per snack than `b` if `(current - a) / a.len() > (current - b) / b.len()`;
+        // ties are broken in favor of the combination getting the closest
+        let (snacks, next_color, _) = (1..=depth)
+            .filter_map(|n| best_combination(current_color, final_color, n))
+            .filter(|(_, _, next_distance)| *next_distance < current_distance)
+            .max_by(|(a, _, a_distance), (b, _, b_distance)| {
+                let a_progress = u64::from(current_distance - a_distance) * b.len() as u64;
+                let b_progress = u64::from(current_distance - b_distance) * a.len() as u64;
+
+                a_progress.cmp(&b_progress).then(b_distance.cmp(a_distance))
+            })?;
+
+        meal.extend(snacks);
+        current_color = next_color;
+    }
+
+    Some(meal)
+}
+
+/// Returns the `n` snacks bringing `current_color` the closest to `final_color`, along with the color reached
+/// and its distance to `final_color`. No snack is ever combined with its opposite.
+fn best_combination(current_color: Rgb, final_color: Rgb, n: usize) -> Option<(Vec<Snack>, Rgb, u32)> {
+    /// Calls `f` with every combination of `n` snacks starting with `combination`, in the order of [`Snack::VALUES`].
+    fn combinations(combination: &mut Vec<Snack>, from: usize, n: usize, f: &mut impl FnMut(&[Snack])) {
+        if combination.len() == n {
+            f(combination);
+            return;
+        }
+
+        for (i, snack) in Snack::VALUES.into_iter().enumerate().skip(from) {
+            if !combination.contains(&-snack) {
+                combination.push(snack);
+                combinations(combination, i, n, f);
+                combination.pop();
+            }
+        }
+    }
+
+    let mut best: Option<(Vec<Snack>, Rgb, u32)> = None;
+
+    combinations(&mut Vec::with_capacity(n), 0, n, &mut |snacks| {
+        if let Some(next_color) = snacks.iter().try_fold(current_color, |color, snack| snack.alter(color)) {
+            let next_distance = next_color.distance(final_color);
+
+            if best.as_ref().map_or(true, |(_, _, distance)| next_distance < *distance) {
+                best = Some((snacks.to_vec(), next_color, next_distance));
+            }
+        }
+    });
+
+    best
+}
+
+/// The greedy search behind [`make_meal`], [`make_meal_few_kinds`], [`make_meal_from_color`], [`make_meal_to_color`] and [`make_meal_distances`].
+///
+/// Returns the meal, the color reached and the distance to `final_color` after each step.
+///
+/// If `final_dye` is `Some`, the search stops once the current color is closer to it than to any other dye;
+/// otherwise, or if this dye can't be reached from `starting_color`, it stops once `final_color` can no longer be approached.
+/// If `prefer_eaten` is `true`, ties are broken in favor of the snacks already contained in the meal.
+///
+/// At each step, two snacks are tried at once if `depth` is at least `2` and a single snack can no longer get any closer.
+fn greedy_meal(starting_color: Rgb, final_color: Rgb, final_dye: Option<Dye>, prefer_eaten: bool, depth: usize) -> (Vec<Snack>, Rgb, Vec<u32>) {
+    let mut meal = Vec::new();
+    let mut distances = Vec::new();
+
+    let mut current_color = starting_color;
     let mut current_distance = current_color.distance(final_color);
 
     loop {
@@ -155,6 +539,11 @@ pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
             fn from(snacks: [Snack; N], current_color: Rgb, final_color: Rgb) -> Option<Possibility<N>> {
                 snacks.iter().copied().try_fold(current_color, |current_color, snack| snack.alter(current_color)).map(|next_color| Possibility { snacks,  next_color, next_distance: next_color.distance(final_color) })
             }
+            
+            // sort by distance, then by new kinds of snack if `eaten` is some
+            fn key(&self, eaten: Option<&[Snack]>) -> (u32, usize) {
+                (self.next_distance, eaten.map_or(0, |eaten| self.snacks.iter().filter(|s| !eaten.contains(s)).count()))
+            }
         }
         
         impl Possibility<1> {
@@ -162,8 +551,8 @@ pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
                 Snack::VALUES.into_iter().filter_map(move |s| Self::from([s], current_color, final_color))
             }
             
-            fn get(current_color: Rgb, final_color: Rgb) -> Possibility<1> {
-                Self::iter(current_color, final_color).min_by_key(|p| p.next_distance).unwrap()
+            fn get(current_color: Rgb, final_color: Rgb, eaten: Option<&[Snack]>) -> Option<Possibility<1>> {
+                Self::iter(current_color, final_color).min_by_key(|p| p.key(eaten))
             }
         }
         
@@ -190,19 +579,28 @@ pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
                 USED_PAIRS.into_iter().filter_map(move |(s, t)| Self::from([s, t], current_color, final_color))
             }
             
-            fn get(current_color: Rgb, final_color: Rgb) -> Possibility<2> {
-                Self::iter(current_color, final_color).min_by_key(|p| p.next_distance).unwrap()
+            fn get(current_color: Rgb, final_color: Rgb, eaten: Option<&[Snack]>) -> Option<Possibility<2>> {
+                Self::iter(current_color, final_color).min_by_key(|p| p.key(eaten))
             }
         }
         
         macro_rules! try_possibilities {
             ($N:literal, $($M:literal),*) => { #[allow(clippy::redundant_else)] {
-                let best_choice = Possibility::<$N>::get(current_color, final_color);
+                // an arbitrary color must be strictly approached, as there is no dye to stop at;
+                // it may also be surrounded by overflows
+                let best_choice = Possibility::<$N>::get(current_color, final_color, prefer_eaten.then_some(meal.as_slice()))
+                    .filter(|p| p.next_distance < current_distance || (final_dye.is_some() && p.next_distance == current_distance));
                 
-                if current_distance < best_choice.next_distance {
+                if let Some(best_choice) = best_choice {
+                    meal.extend(best_choice.snacks);
+                    current_color = best_choice.next_color;
+                    current_distance = best_choice.next_distance;
+                    distances.push(current_distance);
+                }
+                else {
                     let current_dye = Dye::try_from(current_color).unwrap_or_else(identity);
                     
-                    if current_dye == final_dye {
+                    if final_dye == Some(current_dye) || $N >= depth {
                         break;
                     }
                     else {
@@ -210,16 +608,15 @@ pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
                         try_possibilities! { $($M),* }
                     }
                 }
-                else {
-                    meal.extend(best_choice.snacks);
-                    current_color = best_choice.next_color;
-                    current_distance = best_choice.next_distance;
-                }
             }};
             
             ($N:literal) => {{ try_possibilities! { $N, } }};
             
-            () => {{ unreachable!("Possibility<3>") }};
+            () => {{
+                // every dye can be reached from every other dye, but not necessarily from an arbitrary color
+                debug_assert!(final_dye.is_none() || Dye::try_from(starting_color).is_err(), "Possibility<3>");
+                break;
+            }};
         }
         
         // try using one snack, or two if one snack can no longer
@@ -227,9 +624,12 @@ pub fn make_meal(starting_dye: Dye, final_dye: Dye) -> Vec<Snack> {
         try_possibilities! { 1, 2 }
     }
 
-    meal
+    (meal, current_color, distances)
 }
 
+/// The [URL-safe base64](https://datatracker.ietf.org/doc/html/rfc4648#section-5) alphabet, used by [`SnackList::to_compact`].
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
 /// An unsorted list of [`Snack`], can be considered an `EnumMap<Snack, u8>`.
 ///
 /// This struct is stored as a [`NonZeroU64`], enabling some memory layout optimization:
@@ -313,73 +713,352 @@ impl SnackList {
         
         count
     }
-}
 
-impl From<&[Snack]> for SnackList {
-    /// Creates a new [`SnackList`] from a slice of [`Snack`].
-    fn from(snacks: &[Snack]) -> SnackList {
-        let mut sl = SnackList::new().0.get();
+    /// Removes the pairs of opposite snacks from `self`, such as an [`Apple`](Snack::Apple) and a [`Plum`](Snack::Plum),
+    /// as their effects nullify each other.
+    ///
+    /// The returned list changes a color the same way as `self` once all its snacks are eaten, with fewer snacks;
+    /// it never contains both a snack and its opposite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// let snacks = SnackList::from([Snack::Apple, Snack::Plum, Snack::Apple, Snack::Pear, Snack::Apple].as_slice());
+    /// let canonical = snacks.canonicalize();
+    ///
+    /// assert_eq!(canonical.get(Snack::Apple), 2);
+    /// assert_eq!(canonical.get(Snack::Plum), 0);
+    /// assert_eq!(canonical.get(Snack::Pear), 1);
+    /// ```
+    #[must_use]
+    pub fn canonicalize(mut self) -> SnackList {
+        for snack in [Snack::Apple, Snack::Pear, Snack::Berries] {
+            let n = self.get(snack).min(self.get(-snack));
 
-        for snack in snacks {
-            sl += 1 << (8 * *snack as usize);
+            self.set(snack, self.get(snack) - n);
+            self.set(-snack, self.get(-snack) - n);
         }
 
-        SnackList(NonZeroU64::new(sl).expect("integer overflow"))
+        self
     }
-}
 
-impl From<SnackList> for [(Snack, u8); 6] {
-    fn from(value: SnackList) -> [(Snack, u8); 6] {
-        [
-            (Snack::Apple,     ((value.0.get()      ) & 0xFF) as u8),
-            (Snack::Pear,      ((value.0.get() >>  8) & 0xFF) as u8),
-            (Snack::Berries,   ((value.0.get() >> 16) & 0xFF) as u8),
-            (Snack::Plum,      ((value.0.get() >> 24) & 0xFF) as u8),
-            (Snack::Fruit,     ((value.0.get() >> 32) & 0xFF) as u8),
-            (Snack::Pineapple, ((value.0.get() >> 40) & 0xFF) as u8)
-        ]
-    }
-}
+    /// Returns the darkest and the brightest values each component of a color starting at `start` could take while
+    /// eating the snacks of `self`, in any order, as `(min, max)`.
+    ///
+    /// A component is the smallest once all the snacks decreasing it have been eaten before the others, and the largest
+    /// once all the snacks increasing it have. The bounds are saturated, so a component of `0` or `255` may hide an
+    /// overflow in some orders; [`SnackList::always_overflows`] tells whether every order overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Rgb, Snack, SnackList};
+    ///
+    /// let snacks = SnackList::from([Snack::Apple, Snack::Apple, Snack::Pear].as_slice());
+    ///
+    /// assert_eq!(snacks.reachable_bounds(Rgb::gray(100)), (Rgb::new(95, 90, 85), Rgb::new(110, 105, 100)));
+    /// ```
+    #[must_use]
+    pub fn reachable_bounds(&self, start: Rgb) -> (Rgb, Rgb) {
+        let (min, max) = self.unsaturated_bounds(start);
+        let saturate = |c: [i32; 3]| c.map(|c| u8::try_from(c.max(0)).unwrap_or(u8::MAX));
 
-impl IntoIterator for SnackList {
-    type Item = (Snack, u8);
-    type IntoIter = array::IntoIter<Self::Item, 6>;
+        (Rgb::from(saturate(min)), Rgb::from(saturate(max)))
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        <SnackList as Into<[Self::Item; 6]>>::into(self).into_iter()
+    /// Returns `true` if feeding all the snacks of `self` to a chocobo of color `start` overflows a component
+    /// whatever the order, as the final color is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Rgb, Snack, SnackList};
+    ///
+    /// let snacks = SnackList::from([Snack::Apple, Snack::Plum, Snack::Apple].as_slice());
+    ///
+    /// assert!(snacks.always_overflows(Rgb::new(252, 100, 100)));
+    /// assert!(!snacks.always_overflows(Rgb::new(245, 100, 100)));
+    /// ```
+    #[must_use]
+    pub fn always_overflows(&self, start: Rgb) -> bool {
+        self.final_color(start).is_none()
     }
-}
 
-impl Default for SnackList {
-    /// Creates a new, empty `SnackList`.
-    #[inline]
-    fn default() -> SnackList {
-        SnackList::new()
+    /// Returns the color of a chocobo of color `start` after eating all the snacks of `self`, whatever the order,
+    /// or `None` if it is out of bounds.
+    fn final_color(self, start: Rgb) -> Option<Rgb> {
+        let (r, g, b) = self.into_iter().fold((0, 0, 0), |(r, g, b), (snack, count)| {
+            let (dr, dg, db) = snack.effect();
+            (r + i32::from(dr) * i32::from(count), g + i32::from(dg) * i32::from(count), b + i32::from(db) * i32::from(count))
+        });
+
+        Some(Rgb::new(
+            u8::try_from(i32::from(start.r) + r).ok()?,
+            u8::try_from(i32::from(start.g) + g).ok()?,
+            u8::try_from(i32::from(start.b) + b).ok()?
+        ))
     }
-}
 
-impl fmt::Debug for SnackList {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut dm = f.debug_map();
+    /// The unsaturated bounds behind [`SnackList::reachable_bounds`].
+    fn unsaturated_bounds(self, start: Rgb) -> ([i32; 3], [i32; 3]) {
+        let mut min = [start.r, start.g, start.b].map(i32::from);
+        let mut max = min;
 
-        for snack in Snack::VALUES {
-            dm.entry(&snack, &self.get(snack));
+        for (snack, count) in self {
+            let (r, g, b) = snack.effect();
+
+            for (i, delta) in [r, g, b].into_iter().enumerate() {
+                let delta = i32::from(delta) * i32::from(count);
+
+                if delta < 0 {
+                    min[i] += delta;
+                }
+                else {
+                    max[i] += delta;
+                }
+            }
         }
 
-        dm.finish()
+        (min, max)
     }
-}
 
-/// Reduces the complexity of a meal made with [`make_meal`] while preserving the same change of plumage.
-///
-/// The current implementation is a [backtracking](https://en.wikipedia.org/wiki/Backtracking) algorithm;
-/// for each type of [`Snack`], it tries to remove as many as possible that won't overflow the [color](`Rgb`),
-/// and then recurses until there are no more snacks.
-/// It then returns the candidate with the fewest groups.
-///
-/// # Examples
-///
-/// ```
+    /// Groups the snacks of `self` by their [source region](Snack::source_region), skipping the snacks not contained.
+    ///
+    /// The regions are in the order of their first snack in [`Snack::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// let snacks = SnackList::from([Snack::Plum, Snack::Apple, Snack::Plum].as_slice());
+    ///
+    /// assert_eq!(snacks.by_region(), [
+    ///     ("Xelphatol", vec![(Snack::Apple, 1)]),
+    ///     ("Doma", vec![(Snack::Plum, 2)])
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn by_region(&self) -> Vec<(&'static str, Vec<(Snack, u8)>)> {
+        let mut regions: Vec<(&'static str, Vec<(Snack, u8)>)> = Vec::new();
+
+        for (snack, count) in self.into_iter().filter(|(_, count)| *count > 0) {
+            let region = snack.source_region();
+
+            match regions.iter_mut().find(|(r, _)| *r == region) {
+                Some((_, snacks)) => snacks.push((snack, count)),
+                None => regions.push((region, vec![(snack, count)]))
+            }
+        }
+
+        regions
+    }
+
+    /// Encodes `self` into a short string, suitable for URLs.
+    ///
+    /// The six counts are encoded in [URL-safe base64](https://datatracker.ietf.org/doc/html/rfc4648#section-5),
+    /// without padding, always giving eight characters. [`SnackList::from_compact`] decodes them back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// let snacks = SnackList::from([Snack::Apple, Snack::Apple, Snack::Plum].as_slice());
+    ///
+    /// assert_eq!(SnackList::new().to_compact(), "AAAAAAAA");
+    /// assert_eq!(snacks.to_compact().len(), 8);
+    /// assert_eq!(SnackList::from_compact(&snacks.to_compact()), Some(snacks));
+    /// ```
+    #[must_use]
+    pub fn to_compact(self) -> String {
+        let bits = Snack::VALUES.into_iter().fold(0_u64, |bits, snack| (bits << 8) | u64::from(self.get(snack)));
+
+        (0..8).rev().map(|i| char::from(BASE64_URL[((bits >> (6 * i)) & 0x3F) as usize])).collect()
+    }
+
+    /// Decodes a string created by [`SnackList::to_compact`], or returns `None` if `s` is malformed,
+    /// i.e. not made of exactly eight URL-safe base64 characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// assert_eq!(SnackList::from_compact("AAAAAAAA"), Some(SnackList::new()));
+    /// assert_eq!(SnackList::from_compact("AQAAAAAA").map(|snacks| snacks.get(Snack::Apple)), Some(1));
+    ///
+    /// assert_eq!(SnackList::from_compact("AAAA"), None);
+    /// assert_eq!(SnackList::from_compact("AAAAAAA="), None);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_compact(s: &str) -> Option<SnackList> {
+        if s.len() != 8 {
+            return None;
+        }
+
+        let bits = s.bytes().try_fold(0_u64, |bits, c| {
+            let digit = BASE64_URL.iter().position(|d| *d == c)?;
+            Some((bits << 6) | digit as u64)
+        })?;
+
+        let mut snacks = SnackList::new();
+        for (i, snack) in Snack::VALUES.into_iter().rev().enumerate() {
+            snacks.set(snack, (bits >> (8 * i)) as u8);
+        }
+
+        Some(snacks)
+    }
+
+    /// Returns a new `SnackList` containing, for each [`Snack`], the largest count of `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// let have = SnackList::from([Snack::Apple, Snack::Apple, Snack::Pear].as_slice());
+    /// let need = SnackList::from([Snack::Apple, Snack::Plum].as_slice());
+    ///
+    /// assert_eq!(have.union(need), SnackList::from([Snack::Apple, Snack::Apple, Snack::Pear, Snack::Plum].as_slice()));
+    /// ```
+    #[must_use]
+    pub fn union(self, other: SnackList) -> SnackList {
+        let mut union = self;
+
+        for snack in Snack::VALUES {
+            union.set(snack, self.get(snack).max(other.get(snack)));
+        }
+
+        union
+    }
+
+    /// Returns a new `SnackList` containing, for each [`Snack`], the smallest count of `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Snack, SnackList};
+    ///
+    /// let have = SnackList::from([Snack::Apple, Snack::Apple, Snack::Pear].as_slice());
+    /// let need = SnackList::from([Snack::Apple, Snack::Plum].as_slice());
+    ///
+    /// assert_eq!(have.intersection(need), SnackList::from([Snack::Apple].as_slice()));
+    /// ```
+    #[must_use]
+    pub fn intersection(self, other: SnackList) -> SnackList {
+        let mut intersection = self;
+
+        for snack in Snack::VALUES {
+            intersection.set(snack, self.get(snack).min(other.get(snack)));
+        }
+
+        intersection
+    }
+}
+
+impl From<&[Snack]> for SnackList {
+    /// Creates a new [`SnackList`] from a slice of [`Snack`].
+    fn from(snacks: &[Snack]) -> SnackList {
+        let mut sl = SnackList::new().0.get();
+
+        for snack in snacks {
+            sl += 1 << (8 * *snack as usize);
+        }
+
+        SnackList(NonZeroU64::new(sl).expect("integer overflow"))
+    }
+}
+
+impl From<SnackList> for [(Snack, u8); Snack::COUNT] {
+    fn from(value: SnackList) -> [(Snack, u8); Snack::COUNT] {
+        [
+            (Snack::Apple,     ((value.0.get()      ) & 0xFF) as u8),
+            (Snack::Pear,      ((value.0.get() >>  8) & 0xFF) as u8),
+            (Snack::Berries,   ((value.0.get() >> 16) & 0xFF) as u8),
+            (Snack::Plum,      ((value.0.get() >> 24) & 0xFF) as u8),
+            (Snack::Fruit,     ((value.0.get() >> 32) & 0xFF) as u8),
+            (Snack::Pineapple, ((value.0.get() >> 40) & 0xFF) as u8)
+        ]
+    }
+}
+
+impl IntoIterator for SnackList {
+    type Item = (Snack, u8);
+    type IntoIter = SnackListIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SnackListIter(<SnackList as Into<[Self::Item; Snack::COUNT]>>::into(self).into_iter())
+    }
+}
+
+/// An iterator over the snacks of a [`SnackList`] and their count, in the order of [`Snack::VALUES`].
+///
+/// This struct is created by the [`into_iter`](IntoIterator::into_iter) method on [`SnackList`].
+#[derive(Debug, Clone)]
+pub struct SnackListIter(array::IntoIter<(Snack, u8), { Snack::COUNT }>);
+
+impl Iterator for SnackListIter {
+    type Item = (Snack, u8);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for SnackListIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for SnackListIter {}
+
+impl FusedIterator for SnackListIter {}
+
+impl Default for SnackList {
+    /// Creates a new, empty `SnackList`.
+    #[inline]
+    fn default() -> SnackList {
+        SnackList::new()
+    }
+}
+
+impl fmt::Debug for SnackList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut dm = f.debug_map();
+
+        for snack in Snack::VALUES {
+            dm.entry(&snack, &self.get(snack));
+        }
+
+        dm.finish()
+    }
+}
+
+/// Reduces the complexity of a meal made with [`make_meal`] while preserving the same change of plumage.
+///
+/// The current implementation is a [backtracking](https://en.wikipedia.org/wiki/Backtracking) algorithm;
+/// for each type of [`Snack`], it tries to remove as many as possible that won't overflow the [color](`Rgb`),
+/// and then recurses until there are no more snacks.
+/// It then returns the candidate with the fewest groups.
+///
+/// The result is deterministic: snacks are tried in the order of [`Snack::VALUES`] at each step,
+/// and among the candidates having the fewest groups, the first one found is returned.
+///
+/// # Examples
+///
+/// ```
 /// use chocodye::{Dye, make_meal, make_menu, Snack::*, SnackList};
 ///
 /// let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
@@ -475,96 +1154,1364 @@ pub fn make_menu(starting_dye: Dye, snacks: SnackList) -> Vec<(Snack, u8)> {
     backtrack(snacks, starting_dye.color(), Vec::new())
 }
 
-#[cfg(test)]
-mod lib {
-    mod test {
-        use std::convert::identity;
-        
-        use super::super::*;
-        
-        #[test]
-        fn snacklist_get_set() {
-            let mut list = SnackList::new();
-            assert_eq!(list.0.get(), 1 << 63);
-            assert!(list.is_empty());
-            assert_eq!(list.sum(), 0);
-            assert_eq!(list.kinds(), 0);
-
-            list.set(Snack::Pear, 210);
-            list.add(Snack::Pear, 12);
-            assert_ne!(list.0.get(), 1 << 63);
-            assert_eq!(list.get(Snack::Pear), 222);
-            assert!(!list.is_empty());
-            assert_eq!(list.sum(), 222);
-            assert_eq!(list.kinds(), 1);
+/// Returns the groups of [`make_menu`] one at a time, in the same order.
+///
+/// The menu is still computed as a whole when the iterator is created, as the backtracking needs all the snacks;
+/// this is merely a convenience for iterator chains.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, menu_groups_iter, Snack::*, SnackList};
+///
+/// let snacks = SnackList::from(make_meal(Dye::BarkBrown, Dye::MesaRed).as_slice());
+/// let mut groups = menu_groups_iter(Dye::BarkBrown, snacks);
+///
+/// assert_eq!(groups.next(), Some((Apple, 7)));
+/// assert_eq!(groups.next(), Some((Pear, 3)));
+/// assert_eq!(groups.next(), None);
+/// ```
+pub fn menu_groups_iter(starting_dye: Dye, snacks: SnackList) -> impl Iterator<Item = (Snack, u8)> {
+    make_menu(starting_dye, snacks).into_iter()
+}
 
-            list.set(Snack::Pear, 0);
-            assert_eq!(list.0.get(), 1 << 63);
-            assert!(list.is_empty());
-            assert_eq!(list.sum(), 0);
-            assert_eq!(list.kinds(), 0);
-        }
+/// Like [`make_menu`], but first removes the snacks whose effects nullify each other, minimizing the total number of snacks.
+///
+/// The resulting plumage is the same, but the returned menu may not eat all of `snacks`; an apple and a plum
+/// together have no effect, and are therefore both removed. The remaining snacks are then grouped with [`make_menu`],
+/// which minimizes the number of groups; use it directly if all the snacks must be eaten.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_menu, make_menu_min_total, Snack::*, SnackList};
+///
+/// let mut snacks = SnackList::new();
+/// snacks.set(Apple, 3);
+/// snacks.set(Plum, 1);
+///
+/// assert_eq!(make_menu(Dye::BarkBrown, snacks), [(Apple, 3), (Plum, 1)]);
+/// assert_eq!(make_menu_min_total(Dye::BarkBrown, snacks), [(Apple, 2)]);
+/// ```
+#[must_use]
+pub fn make_menu_min_total(starting_dye: Dye, snacks: SnackList) -> Vec<(Snack, u8)> {
+    make_menu(starting_dye, snacks.canonicalize())
+}
 
-        #[test]
-        fn snacklist_into_array() {
-            let mut list = SnackList::new();
-            list.set(Snack::Pear, 1);
-            list.set(Snack::Pineapple, 2);
-            list.set(Snack::Fruit, 3);
-            list.set(Snack::Plum, 4);
-            list.set(Snack::Berries, 5);
-            list.set(Snack::Apple, 6);
+/// Creates a menu like [`make_menu`], along with the color of the chocobo after each group of snacks has been eaten.
+///
+/// If `snacks` comes from a meal towards a dye, e.g. with [`shopping_list`], the last color is the one reached by
+/// that meal. As snacks move the color by steps of five, it is usually not exactly the dye's color, but the dye is
+/// the [nearest](Dye::try_from) to it.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, make_menu_with_colors, Rgb, Snack::*, SnackList};
+///
+/// let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
+/// let menu = make_menu_with_colors(Dye::BarkBrown, SnackList::from(meal.as_slice()));
+///
+/// assert_eq!(menu, [(Apple, 7, Rgb::new(141, 40, 20)), (Pear, 3, Rgb::new(126, 55, 5))]);
+/// assert_eq!(Dye::try_from(Rgb::new(126, 55, 5)), Err(Dye::MesaRed));
+/// ```
+#[must_use]
+pub fn make_menu_with_colors(starting_dye: Dye, snacks: SnackList) -> Vec<(Snack, u8, Rgb)> {
+    let mut color = starting_dye.color();
 
-            assert_eq!(<SnackList as Into<[(Snack, u8); 6]>>::into(list), [
-                (Snack::Apple, 6),
-                (Snack::Pear, 1),
-                (Snack::Berries, 5),
-                (Snack::Plum, 4),
-                (Snack::Fruit, 3),
-                (Snack::Pineapple, 2)
-            ]);
-            
-            assert!(!list.is_empty());
-            assert_eq!(list.sum(), 21);
-            assert_eq!(list.kinds(), 6);
-            
-            // see safety note of `SnackList::set`
-            for snack in Snack::VALUES {
-                assert!((snack as u8) < 8);
-            }
-        }
-        
-        #[test]
-        fn all_is_ok() {
-            for src in Dye::VALUES {
-                for dst in Dye::VALUES {
-                    let meal = make_meal(src, dst);
-                    let snacks = SnackList::from(meal.as_slice());
-                    
-                    let mut rgb = src.color();
-                    for snack in meal {
-                        rgb = snack.alter(rgb).unwrap();
-                    }
-                    
-                    let dye = Dye::try_from(rgb).unwrap_or_else(identity);
-                    assert!(dye == dst, "make_meal({src:?}, {dst:?}) returned {dye:?} (d = {})", dye.distance(dst));
-                    
-                    let menu = make_menu(src, snacks);
-                    
-                    let mut rgb = src.color();
-                    for (snack, count) in menu.clone() {
-                        for i in 0..count {
-                            rgb = match snack.alter(rgb) {
-                                Some(rgb) => rgb,
-                                None => panic!("integer overflow on ({:?} {:?}).alter({:?}) (i = {}/{})", snack, snack.effect(), rgb, i, count - 1)
-                            }
-                        }
-                    }
-                    
-                    let dye = Dye::try_from(rgb).unwrap_or_else(identity);
-                    assert!(dye == dst, "make_menu({src:?}, {dst:?}) returned {dye:?} (d = {}, sl = {snacks:#?}, menu = {menu:#?})", dye.distance(dst));
-                }
+    make_menu(starting_dye, snacks).into_iter()
+        .map(|(snack, count)| {
+            for _ in 0..count {
+                color = snack.alter(color).expect("color component overflowed");
             }
+
+            (snack, count, color)
+        })
+        .collect()
+}
+
+/// Splits a menu into what to buy and how to feed it: the net [`SnackList`], whose snacks nullifying each other
+/// have been removed like in [`make_menu_min_total`], and an order in which to feed them without overflowing
+/// any color component.
+///
+/// The feeding order is the menu created by [`make_menu`], ungrouped; it contains exactly the snacks of the returned list.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_menu_parts, Snack::*, SnackList};
+///
+/// let mut snacks = SnackList::new();
+/// snacks.set(Apple, 3);
+/// snacks.set(Plum, 1);
+///
+/// let (list, order) = make_menu_parts(Dye::BarkBrown, snacks);
+///
+/// assert_eq!(list, SnackList::from([Apple, Apple].as_slice()));
+/// assert_eq!(order, [Apple, Apple]);
+/// ```
+#[must_use]
+pub fn make_menu_parts(starting_dye: Dye, snacks: SnackList) -> (SnackList, Vec<Snack>) {
+    let list = snacks.canonicalize();
+
+    let order = make_menu(starting_dye, list).into_iter()
+        .flat_map(|(snack, count)| iter::repeat(snack).take(usize::from(count)))
+        .collect();
+
+    (list, order)
+}
+
+/// Returns the smallest [`SnackList`] whose snacks, once all eaten, change a color by exactly `delta`, regardless of order.
+///
+/// Each snack changes each component by five, so `delta` is only reachable if all its components are multiples of five,
+/// and are either all even or all odd multiples. Returns `None` otherwise, or if a snack would be needed more than 255 times.
+///
+/// This solution is unique, as no two snacks sharing the same effect are ever returned.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Snack, snacks_for_delta};
+///
+/// let snacks = snacks_for_delta((10, -20, 0)).unwrap();
+///
+/// assert_eq!(snacks.get(Snack::Apple), 2);
+/// assert_eq!(snacks.get(Snack::Fruit), 1);
+/// assert_eq!(snacks.get(Snack::Berries), 1);
+/// assert_eq!(snacks.sum(), 4);
+///
+/// assert!(snacks_for_delta((5, 0, 0)).is_none());
+/// ```
+#[must_use]
+pub fn snacks_for_delta(delta: (i32, i32, i32)) -> Option<SnackList> {
+    let (r, g, b) = delta;
+
+    if r % 5 != 0 || g % 5 != 0 || b % 5 != 0 {
+        return None;
+    }
+
+    let (r, g, b) = (r / 5, g / 5, b / 5);
+
+    if (r + g) % 2 != 0 || (g + b) % 2 != 0 {
+        return None;
+    }
+
+    // Apple, Pear and Berries are linearly independent, so the net count of each pair of opposite snacks is unique:
+    // r = a - p - b, g = -a + p - b, b = -a - p + b
+    let mut snacks = SnackList::new();
+
+    for (snack, n) in [(Snack::Apple, -(g + b) / 2), (Snack::Pear, -(r + b) / 2), (Snack::Berries, -(r + g) / 2)] {
+        let (snack, n) = if n < 0 { (-snack, -n) } else { (snack, n) };
+        snacks.set(snack, u8::try_from(n).ok()?);
+    }
+
+    Some(snacks)
+}
+
+/// The highest number of states visited by [`safe_feed_order`] before giving up.
+const SAFE_FEED_ORDER_MAX_STATES: usize = 1 << 16;
+
+/// Orders `snacks` so that no color component overflows when they are fed one by one to a chocobo of color `starting_color`,
+/// or returns `Ok(None)` if there is no such order.
+///
+/// If the final color is out of bounds, or if no snack can be eaten last without overflowing just before, there is no such
+/// order; if no order can overflow, the snacks are returned in the order of [`Snack::VALUES`]. Otherwise, the current
+/// implementation is a [depth-first search](https://en.wikipedia.org/wiki/Depth-first_search), trying first the snacks
+/// that keep the color the furthest from overflowing, and remembering the dead ends.
+/// Unlike [`make_menu`], the snacks are not grouped.
+///
+/// # Errors
+///
+/// Returns [`SearchLimitError`] if the search visits 65536 states without finding an order nor proving that there is none.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Rgb, safe_feed_order, Snack::*, SnackList};
+///
+/// let snacks = SnackList::from([Apple, Apple, Plum].as_slice());
+/// let starting_color = Rgb::new(250, 128, 128);
+///
+/// assert_eq!(Apple.alter(Apple.alter(starting_color).unwrap()), None);
+/// assert_eq!(safe_feed_order(starting_color, snacks), Ok(Some(vec![Plum, Apple, Apple])));
+///
+/// assert_eq!(safe_feed_order(Rgb::RED, SnackList::from([Apple].as_slice())), Ok(None));
+/// ```
+pub fn safe_feed_order(starting_color: Rgb, snacks: SnackList) -> Result<Option<Vec<Snack>>, SearchLimitError> {
+    safe_feed_order_within(starting_color, snacks, SAFE_FEED_ORDER_MAX_STATES)
+}
+
+/// Like [`safe_feed_order`], but giving up after visiting `max_states` states.
+fn safe_feed_order_within(starting_color: Rgb, snacks: SnackList, max_states: usize) -> Result<Option<Vec<Snack>>, SearchLimitError> {
+    /// Pushes the remaining snacks into `order`, returning `Ok(false)` if it is a dead end.
+    fn dfs(current_color: Rgb, final_color: Rgb, remaining: SnackList, order: &mut Vec<Snack>, dead_ends: &mut HashSet<SnackList>, remaining_states: &mut usize) -> Result<bool, SearchLimitError> {
+        /// The distance between `color` and the nearest overflow.
+        fn margin(color: Rgb) -> u8 {
+            [color.r, color.g, color.b].into_iter().map(|c| c.min(u8::MAX - c)).min().unwrap_or(0)
+        }
+
+        if remaining.is_empty() {
+            return Ok(true);
+        }
+
+        if dead_ends.contains(&remaining) || !can_end(final_color, remaining) {
+            return Ok(false);
+        }
+
+        if *remaining_states == 0 {
+            return Err(SearchLimitError);
+        }
+
+        *remaining_states -= 1;
+
+        let mut candidates: Vec<(Snack, Rgb)> = remaining.into_iter()
+            .filter(|(_, count)| *count > 0)
+            .filter_map(|(snack, _)| snack.alter(current_color).map(|color| (snack, color)))
+            .collect();
+
+        candidates.sort_by_key(|(_, color)| u8::MAX - margin(*color));
+
+        for (snack, next_color) in candidates {
+            let mut next_remaining = remaining;
+            next_remaining.set(snack, remaining.get(snack) - 1);
+            order.push(snack);
+
+            if dfs(next_color, final_color, next_remaining, order, dead_ends, remaining_states)? {
+                return Ok(true);
+            }
+
+            order.pop();
+        }
+
+        dead_ends.insert(remaining);
+        Ok(false)
+    }
+
+    /// Returns `true` if one of the `remaining` snacks can be eaten last, i.e. if the color before it is in bounds.
+    fn can_end(final_color: Rgb, remaining: SnackList) -> bool {
+        remaining.into_iter().any(|(snack, count)| count > 0 && (-snack).alter(final_color).is_some())
+    }
+
+    let Some(final_color) = snacks.final_color(starting_color) else {
+        return Ok(None);
+    };
+
+    let (min, max) = snacks.unsaturated_bounds(starting_color);
+
+    if min.iter().all(|c| *c >= 0) && max.iter().all(|c| *c <= i32::from(u8::MAX)) {
+        return Ok(Some(snacks.into_iter().flat_map(|(snack, count)| iter::repeat(snack).take(usize::from(count))).collect()));
+    }
+
+    let mut order = Vec::new();
+    let mut remaining_states = max_states;
+
+    Ok(dfs(starting_color, final_color, snacks, &mut order, &mut HashSet::new(), &mut remaining_states)?.then_some(order))
+}
+
+/// An error that can be returned when a search gives up before reaching a conclusion.
+///
+/// This error is used as the error type for the [`safe_feed_order`] function.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SearchLimitError;
+
+impl fmt::Display for SearchLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("search limit exceeded")
+    }
+}
+
+impl Error for SearchLimitError {}
+
+/// Returns how many snacks of each kind must be bought to change a chocobo's plumage from one [`Dye`] to another.
+///
+/// This is the meal created by [`make_meal`] as a [`SnackList`], and contains as many snacks as the menu
+/// created by [`make_menu`].
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, shopping_list, Snack};
+///
+/// let list = shopping_list(Dye::BarkBrown, Dye::MesaRed);
+///
+/// assert_eq!(list.get(Snack::Apple), 7);
+/// assert_eq!(list.get(Snack::Pear), 3);
+/// assert_eq!(list.sum(), 10);
+/// ```
+#[must_use]
+pub fn shopping_list(starting_dye: Dye, final_dye: Dye) -> SnackList {
+    SnackList::from(make_meal(starting_dye, final_dye).as_slice())
+}
+
+/// Groups consecutive identical snacks of a meal, preserving their order.
+///
+/// Unlike [`make_menu`], snacks are never reordered; this is a simple [run-length encoding](https://en.wikipedia.org/wiki/Run-length_encoding),
+/// useful for step-by-step displays. Runs longer than 255 snacks are split.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{compress_meal, Snack::*};
+///
+/// assert_eq!(compress_meal(&[Apple, Apple, Pear, Apple]), [(Apple, 2), (Pear, 1), (Apple, 1)]);
+/// ```
+#[must_use]
+pub fn compress_meal(meal: &[Snack]) -> Vec<(Snack, u8)> {
+    let mut compressed: Vec<(Snack, u8)> = Vec::new();
+
+    for &snack in meal {
+        match compressed.last_mut() {
+            Some((last, count)) if *last == snack && *count < u8::MAX => *count += 1,
+            _ => compressed.push((snack, 1))
+        }
+    }
+
+    compressed
+}
+
+/// Returns the color a chocobo would have after eating all the snacks of `meal`, in order,
+/// or `None` if any color component had overflowed.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{apply_meal, Dye, make_meal, Rgb, Snack};
+///
+/// let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
+/// assert_eq!(apply_meal(Dye::BarkBrown, &meal), Some(Rgb::new(126, 55, 5)));
+///
+/// assert_eq!(apply_meal(Dye::LotusPink, &[Snack::Apple]), None);
+/// ```
+#[must_use]
+pub fn apply_meal(starting_dye: Dye, meal: &[Snack]) -> Option<Rgb> {
+    meal.iter().try_fold(starting_dye.color(), |color, snack| snack.alter(color))
+}
+
+/// Returns how much each kind of snack changed the color of a chocobo that ate `meal`, indexed by `Snack as usize`.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{meal_contributions, Snack::*};
+///
+/// let contributions = meal_contributions(&[Apple, Apple, Pear, Apple]);
+///
+/// assert_eq!(contributions[Apple as usize], (15, -15, -15));
+/// assert_eq!(contributions[Pear as usize], (-5, 5, -5));
+/// assert_eq!(contributions[Plum as usize], (0, 0, 0));
+/// ```
+#[must_use]
+pub fn meal_contributions(meal: &[Snack]) -> [(i32, i32, i32); Snack::COUNT] {
+    let mut contributions = [(0, 0, 0); Snack::COUNT];
+
+    for &snack in meal {
+        let (r, g, b) = snack.effect();
+        let (cr, cg, cb) = &mut contributions[snack as usize];
+
+        *cr += i32::from(r);
+        *cg += i32::from(g);
+        *cb += i32::from(b);
+    }
+
+    contributions
+}
+
+/// Returns the dye a chocobo would have after eating all the snacks of `meal`, in order.
+///
+/// Like [`Dye::try_from`], returns `Ok` if the resulting color is exactly a dye, or `Err` with the closest dye otherwise.
+///
+/// # Panics
+///
+/// Panics if any color component overflows; use [`apply_meal`] to handle this case.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, make_meal, resulting_dye};
+///
+/// let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
+/// assert_eq!(resulting_dye(Dye::BarkBrown, &meal), Err(Dye::MesaRed));
+/// ```
+#[allow(clippy::missing_errors_doc)]
+pub fn resulting_dye(starting_dye: Dye, meal: &[Snack]) -> Result<Dye, Dye> {
+    Dye::try_from(apply_meal(starting_dye, meal).expect("color component overflowed"))
+}
+
+/// Writes the localized menu changing a chocobo's plumage from one [`Dye`] to another, as printed by the `menu` example.
+///
+/// `snacks` is usually the meal created by [`make_meal`]. The menu is written directly into `w`, without
+/// building an intermediate [`String`].
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, Lang, make_meal, SnackList, write_menu};
+///
+/// let snacks = SnackList::from(make_meal(Dye::SnowWhite, Dye::BoneWhite).as_slice());
+///
+/// let mut buf = Vec::new();
+/// write_menu(&mut buf, &Lang::English.into_bundle(), Dye::SnowWhite, Dye::BoneWhite, snacks).unwrap();
+///
+/// assert!(String::from_utf8(buf).unwrap().starts_with("Required Fruits:\n"));
+/// ```
+///
+/// # Errors
+///
+/// Returns any error encountered while writing into `w`.
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+pub fn write_menu(w: &mut impl io::Write, bundle: &FluentBundle, starting_dye: Dye, final_dye: Dye, snacks: SnackList) -> io::Result<()> {
+    writeln!(w, "{}", message!(bundle, "required-fruits"))?;
+    for (snack, count) in snacks.into_iter().filter(|(_, count)| *count > 0) {
+        writeln!(w, "– {}", snack.quantified_name(bundle, count.into()))?;
+    }
+
+    if snacks.is_empty() {
+        writeln!(w, "{}", message!(bundle, "none"))?;
+    }
+    else {
+        writeln!(w)?;
+
+        writeln!(w, "{}", message!(bundle, "feed-order"))?;
+        for (snack, count) in make_menu(starting_dye, snacks) {
+            writeln!(w, "– {}", snack.quantified_name(bundle, count.into()))?;
+        }
+
+        let ds = starting_dye.distance(final_dye);
+        let dd = Dye::DEFAULT_CHOCOBO_COLOR.distance(final_dye);
+
+        if ds > dd {
+            let ss = snacks.sum();
+            let ds = make_meal(Dye::DEFAULT_CHOCOBO_COLOR, final_dye).len();
+
+            writeln!(w)?;
+            writeln!(w, "{}", message!(bundle, "han-lemon-note", { "ratio" = format!("{:.1}", 100_f32 * (1_f32 - (ds as f32 / ss as f32))) }))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a localized recipe card for changing a chocobo's plumage from one [`Dye`] to another, to be shared as is.
+///
+/// The card is made of the starting and final dyes' names, followed by their [shopping list](shopping_list)
+/// with the total number of fruits, then by the [feeding order](make_menu).
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, Lang, recipe_card};
+///
+/// let card = recipe_card(&Lang::English.into_bundle(), Dye::BarkBrown, Dye::MesaRed);
+///
+/// assert!(card.starts_with("Bark Brown → Mesa Red\n"));
+/// assert!(card.contains("Total: \u{2068}10\u{2069} fruits\n"));
+/// ```
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[must_use]
+pub fn recipe_card(bundle: &FluentBundle, starting_dye: Dye, final_dye: Dye) -> String {
+    let snacks = shopping_list(starting_dye, final_dye);
+    let item = |(snack, count): (Snack, u8)| format!("– {}", snack.quantified_name(bundle, count.into()));
+
+    let mut lines = vec![
+        format!("{} → {}", starting_dye.color_name(bundle), final_dye.color_name(bundle)),
+        String::new(),
+        message!(bundle, "required-fruits").to_owned()
+    ];
+
+    if snacks.is_empty() {
+        lines.push(message!(bundle, "none").to_owned());
+    }
+    else {
+        lines.extend(snacks.into_iter().filter(|(_, count)| *count > 0).map(item));
+        lines.push(message!(bundle, "total-fruits", { "quantity" = snacks.sum() }));
+
+        lines.push(String::new());
+        lines.push(message!(bundle, "feed-order").to_owned());
+        lines.extend(make_menu(starting_dye, snacks).into_iter().map(item));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Returns every [`Category`] with its localized name, along with its dyes and their localized color names.
+///
+/// The dyes of each category are sorted from the brightest to the darkest. This is the data
+/// behind the `truecolor` example, without any formatting.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Category, Dye, Lang, legend};
+///
+/// let legend = legend(&Lang::English.into_bundle());
+/// let (category, name, dyes) = &legend[0];
+///
+/// assert_eq!(*category, Category::White);
+/// assert_eq!(name, "White Dyes");
+/// assert_eq!(dyes[0], (Dye::SnowWhite, "Snow White".to_owned()));
+/// ```
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[must_use]
+#[allow(clippy::type_complexity)]
+pub fn legend(bundle: &FluentBundle) -> Vec<(Category, String, Vec<(Dye, String)>)> {
+    Category::VALUES.into_iter().map(|category| {
+        let dyes = Dye::sorted_by_luma().into_iter().filter(|dye| dye.category() == category);
+
+        (
+            category,
+            category.full_name(bundle).to_owned(),
+            dyes.map(|dye| (dye, dye.color_name(bundle).to_owned())).collect()
+        )
+    }).collect()
+}
+
+/// A [`Category`] node of the tree returned by [`dye_tree`].
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CategoryNode {
+    /// The category of this node.
+    pub category: Category,
+
+    /// The localized name of the category.
+    pub name: String,
+
+    /// The representative color of the category.
+    pub color: Rgb,
+
+    /// The dyes of the category, from the brightest to the darkest.
+    pub dyes: Vec<DyeLeaf>
+}
+
+/// A [`Dye`] leaf of the tree returned by [`dye_tree`].
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DyeLeaf {
+    /// The dye of this leaf.
+    pub dye: Dye,
+
+    /// The localized color name of the dye.
+    pub name: String,
+
+    /// The color of the dye.
+    pub color: Rgb
+}
+
+/// Returns every [`Category`] with its dyes, as a tree ready to be displayed in a user interface.
+///
+/// This is the same data as [`legend`], with the colors included.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Category, dye_tree, Lang};
+///
+/// let tree = dye_tree(&Lang::English.into_bundle());
+///
+/// assert_eq!(tree[1].category, Category::Red);
+/// assert_eq!(tree[1].name, "Red Dyes");
+/// assert_eq!(tree[1].dyes[0].color, tree[1].dyes[0].dye.color());
+/// ```
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[must_use]
+pub fn dye_tree(bundle: &FluentBundle) -> Vec<CategoryNode> {
+    legend(bundle).into_iter().map(|(category, name, dyes)| CategoryNode {
+        category,
+        name,
+        color: category.color(),
+        dyes: dyes.into_iter().map(|(dye, name)| DyeLeaf { dye, name, color: dye.color() }).collect()
+    }).collect()
+}
+
+/// Returns the localized color names of `dyes` on a single line, separated by spaces,
+/// with [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit) for display in `stdout`.
+///
+/// For more documentation, check the [`ansi_text`] function.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{ansi_dye_row, Dye, Lang};
+/// use std::env;
+///
+/// env::remove_var("COLORTERM");
+///
+/// let row = ansi_dye_row([Dye::SnowWhite, Dye::SootBlack], &Lang::English.into_bundle());
+/// assert_eq!(row, "Snow White Soot Black");
+/// ```
+#[cfg(all(feature = "fluent", feature = "truecolor"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "fluent", feature = "truecolor"))))]
+#[must_use]
+pub fn ansi_dye_row(dyes: impl IntoIterator<Item = Dye>, bundle: &FluentBundle) -> String {
+    ansi_dye_row_with(dyes, bundle, truecolor::terminal_color_support())
+}
+
+/// Like [`ansi_dye_row`], but with the colors supported by the terminal given explicitly.
+#[cfg(all(feature = "fluent", feature = "truecolor"))]
+fn ansi_dye_row_with(dyes: impl IntoIterator<Item = Dye>, bundle: &FluentBundle, support: truecolor::ColorSupport) -> String {
+    dyes.into_iter().map(|dye| truecolor::ansi_text_with(support, dye.color(), dye.color_name(bundle))).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod lib {
+    mod test {
+        use std::convert::identity;
+        
+        use super::super::*;
+        
+        /// Like [`apply_meal`], but starting from an arbitrary color.
+        fn apply_meal_from(starting_color: Rgb, meal: &[Snack]) -> Option<Rgb> {
+            meal.iter().try_fold(starting_color, |color, snack| snack.alter(color))
+        }
+        
+        #[test]
+        fn snacklist_get_set() {
+            let mut list = SnackList::new();
+            assert_eq!(list.0.get(), 1 << 63);
+            assert!(list.is_empty());
+            assert_eq!(list.sum(), 0);
+            assert_eq!(list.kinds(), 0);
+
+            list.set(Snack::Pear, 210);
+            list.add(Snack::Pear, 12);
+            assert_ne!(list.0.get(), 1 << 63);
+            assert_eq!(list.get(Snack::Pear), 222);
+            assert!(!list.is_empty());
+            assert_eq!(list.sum(), 222);
+            assert_eq!(list.kinds(), 1);
+
+            list.set(Snack::Pear, 0);
+            assert_eq!(list.0.get(), 1 << 63);
+            assert!(list.is_empty());
+            assert_eq!(list.sum(), 0);
+            assert_eq!(list.kinds(), 0);
+        }
+
+        #[test]
+        fn snacklist_into_array() {
+            let mut list = SnackList::new();
+            list.set(Snack::Pear, 1);
+            list.set(Snack::Pineapple, 2);
+            list.set(Snack::Fruit, 3);
+            list.set(Snack::Plum, 4);
+            list.set(Snack::Berries, 5);
+            list.set(Snack::Apple, 6);
+
+            assert_eq!(<SnackList as Into<[(Snack, u8); 6]>>::into(list), [
+                (Snack::Apple, 6),
+                (Snack::Pear, 1),
+                (Snack::Berries, 5),
+                (Snack::Plum, 4),
+                (Snack::Fruit, 3),
+                (Snack::Pineapple, 2)
+            ]);
+            
+            let mut iter = list.into_iter();
+            assert_eq!(iter.len(), Snack::COUNT);
+            assert_eq!(iter.next(), Some((Snack::Apple, 6)));
+            assert_eq!(iter.next_back(), Some((Snack::Pineapple, 2)));
+            assert_eq!(iter.len(), Snack::COUNT - 2);
+            
+            assert!(!list.is_empty());
+            assert_eq!(list.sum(), 21);
+            assert_eq!(list.kinds(), 6);
+            
+            // see safety note of `SnackList::set`
+            for snack in Snack::VALUES {
+                assert!((snack as u8) < 8);
+            }
+        }
+        
+        #[test]
+        fn snacklist_union_intersection() {
+            let mut a = SnackList::new();
+            a.set(Snack::Apple, 3);
+            a.set(Snack::Pear, 1);
+            a.set(Snack::Plum, 200);
+            
+            let mut b = SnackList::new();
+            b.set(Snack::Apple, 5);
+            b.set(Snack::Berries, 2);
+            b.set(Snack::Plum, 7);
+            
+            let union = a.union(b);
+            let intersection = a.intersection(b);
+            
+            assert_eq!(<SnackList as Into<[(Snack, u8); 6]>>::into(union), [
+                (Snack::Apple, 5),
+                (Snack::Pear, 1),
+                (Snack::Berries, 2),
+                (Snack::Plum, 200),
+                (Snack::Fruit, 0),
+                (Snack::Pineapple, 0)
+            ]);
+            
+            assert_eq!(<SnackList as Into<[(Snack, u8); 6]>>::into(intersection), [
+                (Snack::Apple, 3),
+                (Snack::Pear, 0),
+                (Snack::Berries, 0),
+                (Snack::Plum, 7),
+                (Snack::Fruit, 0),
+                (Snack::Pineapple, 0)
+            ]);
+            
+            assert_eq!(union, b.union(a));
+            assert_eq!(intersection, b.intersection(a));
+            assert_eq!(a.union(a), a);
+            assert_eq!(a.intersection(SnackList::new()), SnackList::new());
+            assert!(a.intersection(SnackList::new()).is_empty());
+        }
+        
+        #[test]
+        fn all_is_ok() {
+            for src in Dye::VALUES {
+                for dst in Dye::VALUES {
+                    let meal = make_meal(src, dst);
+                    let snacks = SnackList::from(meal.as_slice());
+                    
+                    let dye = resulting_dye(src, &meal).unwrap_or_else(identity);
+                    assert!(dye == dst, "make_meal({src:?}, {dst:?}) returned {dye:?} (d = {})", dye.distance(dst));
+                    
+                    let menu = make_menu(src, snacks);
+                    
+                    let mut rgb = src.color();
+                    for (snack, count) in menu.clone() {
+                        for i in 0..count {
+                            rgb = match snack.alter(rgb) {
+                                Some(rgb) => rgb,
+                                None => panic!("integer overflow on ({:?} {:?}).alter({:?}) (i = {}/{})", snack, snack.effect(), rgb, i, count - 1)
+                            }
+                        }
+                    }
+                    
+                    let dye = Dye::try_from(rgb).unwrap_or_else(identity);
+                    assert!(dye == dst, "make_menu({src:?}, {dst:?}) returned {dye:?} (d = {}, sl = {snacks:#?}, menu = {menu:#?})", dye.distance(dst));
+                }
+            }
+        }
+        
+        #[test]
+        fn few_kinds_is_ok() {
+            for src in Dye::VALUES {
+                for dst in Dye::VALUES {
+                    let meal = make_meal_few_kinds(src, dst);
+                    
+                    let mut rgb = src.color();
+                    for snack in meal {
+                        rgb = snack.alter(rgb).unwrap();
+                    }
+                    
+                    let dye = Dye::try_from(rgb).unwrap_or_else(identity);
+                    assert!(dye == dst, "make_meal_few_kinds({src:?}, {dst:?}) returned {dye:?} (d = {})", dye.distance(dst));
+                }
+            }
+            
+            let meal = SnackList::from(make_meal(Dye::RolanberryRed, Dye::ShadowBlue).as_slice());
+            let few_kinds = SnackList::from(make_meal_few_kinds(Dye::RolanberryRed, Dye::ShadowBlue).as_slice());
+            assert!(few_kinds.kinds() < meal.kinds(), "{few_kinds:?} has more kinds than {meal:?}");
+        }
+        
+        #[test]
+        fn menu_parts_is_ok() {
+            for src in [Dye::SnowWhite, Dye::SootBlack, Dye::BarkBrown, Dye::InkBlue] {
+                for dst in Dye::VALUES {
+                    let meal = make_meal(src, dst);
+                    
+                    let mut snacks = SnackList::from(meal.as_slice());
+                    snacks.set(Snack::Pear, snacks.get(Snack::Pear) + 2);
+                    snacks.set(Snack::Fruit, snacks.get(Snack::Fruit) + 2);
+                    
+                    let (list, order) = make_menu_parts(src, snacks);
+                    
+                    assert_eq!(SnackList::from(order.as_slice()), list);
+                    assert_eq!(apply_meal(src, &order), apply_meal(src, &meal), "{src:?} -> {dst:?}");
+                }
+            }
+        }
+        
+        #[test]
+        fn menu_groups_iter_is_ok() {
+            for src in [Dye::SnowWhite, Dye::BarkBrown, Dye::InkBlue] {
+                for dst in Dye::VALUES {
+                    let snacks = shopping_list(src, dst);
+                    
+                    assert_eq!(menu_groups_iter(src, snacks).collect::<Vec<_>>(), make_menu(src, snacks));
+                }
+            }
+        }
+        
+        #[test]
+        fn by_region_is_ok() {
+            for dst in Dye::VALUES {
+                let snacks = shopping_list(Dye::SnowWhite, dst);
+                let groups = snacks.by_region();
+                
+                let mut seen = SnackList::new();
+                for (region, group) in groups {
+                    for (snack, count) in group {
+                        assert_eq!(snack.source_region(), region);
+                        assert_eq!(seen.get(snack), 0, "{snack:?} appears twice");
+                        seen.set(snack, count);
+                    }
+                }
+                
+                assert_eq!(seen, snacks);
+            }
+        }
+        
+        #[test]
+        fn menu_golden() {
+            use Snack::*;
+            
+            let menu = |src: Dye, dst: Dye| make_menu(src, shopping_list(src, dst));
+            
+            assert_eq!(menu(Dye::BarkBrown, Dye::MesaRed), [(Apple, 7), (Pear, 3)]);
+            assert_eq!(menu(Dye::SnowWhite, Dye::BoneWhite), [(Pear, 4), (Apple, 6)]);
+            assert_eq!(menu(Dye::SalmonPink, Dye::RosePink), [(Berries, 1), (Fruit, 1)]);
+            assert_eq!(menu(Dye::GoobbueGrey, Dye::DesertYellow), [(Apple, 1), (Plum, 1), (Fruit, 3), (Pineapple, 12)]);
+            assert_eq!(menu(Dye::DesertYellow, Dye::InkBlue), [(Pear, 15), (Berries, 28), (Apple, 20), (Pear, 9), (Berries, 6)]);
+            assert_eq!(menu(Dye::SnowWhite, Dye::SootBlack), [(Apple, 5), (Pear, 11), (Berries, 25), (Apple, 25), (Pear, 24), (Berries, 11), (Apple, 5)]);
+        }
+        
+        #[test]
+        #[cfg(feature = "fluent")]
+        fn recipe_card_is_ok() {
+            for lang in Lang::VALUES {
+                let bundle = lang.into_bundle();
+            
+                for (start, end) in [(Dye::SnowWhite, Dye::SootBlack), (Dye::DesertYellow, Dye::InkBlue), (Dye::BarkBrown, Dye::MesaRed)] {
+                    let card = recipe_card(&bundle, start, end);
+                    let total = format!("\u{2068}{}\u{2069}", shopping_list(start, end).sum());
+                
+                    assert!(card.contains(start.color_name(&bundle)), "{card}");
+                    assert!(card.contains(end.color_name(&bundle)), "{card}");
+                    assert!(card.contains(&total), "{card}");
+                    assert!(card.contains(message!(&bundle, "feed-order")), "{card}");
+                }
+            
+                let card = recipe_card(&bundle, Dye::CeruleumBlue, Dye::CeruleumBlue);
+                assert!(card.ends_with(&format!("{}\n", message!(&bundle, "none"))), "{card}");
+            }
+        }
+        
+        #[test]
+        fn canonicalize_is_ok() {
+            let mut snacks = SnackList::new();
+            snacks.set(Snack::Apple, 3);
+            snacks.set(Snack::Plum, 1);
+            
+            let mut expected = SnackList::new();
+            expected.set(Snack::Apple, 2);
+            
+            assert_eq!(snacks.canonicalize(), expected);
+            assert_eq!(SnackList::new().canonicalize(), SnackList::new());
+            
+            for (start, end) in [(Dye::SnowWhite, Dye::SootBlack), (Dye::BarkBrown, Dye::MesaRed)] {
+                let mut snacks = shopping_list(start, end);
+                for snack in Snack::VALUES {
+                    snacks.add(snack, 4);
+                }
+                
+                let canonical = snacks.canonicalize();
+                assert!(Snack::VALUES.into_iter().all(|snack| canonical.get(snack) == 0 || canonical.get(-snack) == 0));
+                
+                let net = |list: SnackList| -> [i32; 3] {
+                    list.into_iter().fold([0; 3], |acc, (snack, count)| {
+                        let (r, g, b) = snack.effect();
+                        [acc[0] + i32::from(r) * i32::from(count), acc[1] + i32::from(g) * i32::from(count), acc[2] + i32::from(b) * i32::from(count)]
+                    })
+                };
+                assert_eq!(net(canonical), net(snacks));
+            }
+        }
+        
+        #[test]
+        fn lookahead_is_ok() {
+            let mut shorter = 0;
+            
+            for starting_dye in Dye::VALUES {
+                for final_dye in Dye::VALUES {
+                    let meal = make_meal(starting_dye, final_dye);
+                    
+                    assert!(meal.starts_with(&make_meal_lookahead(starting_dye, final_dye, 1)));
+                    assert_eq!(make_meal_lookahead(starting_dye, final_dye, 2), meal);
+                    
+                    // deeper searches are slow, so they are only checked from a few starting dyes
+                    let max_depth = if starting_dye as u8 % 8 == 0 { MAX_LOOKAHEAD } else { 3 };
+                    let mut previous_len = meal.len();
+                    
+                    for depth in 3..=max_depth {
+                        let deep = make_meal_lookahead(starting_dye, final_dye, depth);
+                        let reached_dye = Dye::try_from(apply_meal(starting_dye, &deep).unwrap()).unwrap_or_else(identity);
+                        
+                        assert_eq!(reached_dye, final_dye, "{starting_dye:?} -> {final_dye:?} ({depth})");
+                        assert!(deep.len() <= previous_len, "{starting_dye:?} -> {final_dye:?} ({depth})");
+                        
+                        if depth == 3 && deep.len() < meal.len() {
+                            shorter += 1;
+                        }
+                        
+                        previous_len = deep.len();
+                    }
+                }
+            }
+            
+            assert!(shorter > 0);
+            
+            let (snacks, next_color, next_distance) = best_combination(Rgb::gray(128), Rgb::BLACK, 3).unwrap();
+            assert_eq!(snacks.len(), 3);
+            assert!(snacks.iter().all(|snack| !snacks.contains(&-*snack)));
+            assert_eq!(next_distance, next_color.distance(Rgb::BLACK));
+            assert!(next_distance < Rgb::gray(128).distance(Rgb::BLACK));
+        }
+        
+        #[test]
+        fn replan_is_ok() {
+            for starting_dye in Dye::VALUES {
+                for final_dye in Dye::VALUES {
+                    let meal = make_meal(starting_dye, final_dye);
+                    
+                    assert_eq!(replan(&[], starting_dye, final_dye), Some(meal.clone()));
+                    assert_eq!(replan(&meal, starting_dye, final_dye), Some(Vec::new()));
+                    
+                    let (fed, rest) = meal.split_at(meal.len() / 2);
+                    assert_eq!(replan(fed, starting_dye, final_dye).as_deref(), Some(rest));
+                }
+            }
+            
+            assert_eq!(replan(&[Snack::Apple], Dye::LotusPink, Dye::InkBlue), None);
+            assert_eq!(replan(&[Snack::Plum; 60], Dye::SnowWhite, Dye::InkBlue), None);
+        }
+        
+        #[test]
+        fn reachable_bounds_is_ok() {
+            // safe: every order stays within bounds
+            let start = Dye::BarkBrown.color();
+            let snacks = shopping_list(Dye::BarkBrown, Dye::MesaRed);
+            let (min, max) = snacks.reachable_bounds(start);
+            
+            assert!(!snacks.always_overflows(start));
+            assert_eq!(snacks.unsaturated_bounds(start), ([min.r, min.g, min.b].map(i32::from), [max.r, max.g, max.b].map(i32::from)));
+            
+            let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
+            let mut color = start;
+            for snack in meal {
+                color = snack.alter(color).unwrap();
+                assert!((min.r..=max.r).contains(&color.r) && (min.g..=max.g).contains(&color.g) && (min.b..=max.b).contains(&color.b));
+            }
+            
+            // must overflow: the final color is out of bounds
+            let mut snacks = SnackList::new();
+            snacks.set(Snack::Apple, 10);
+            let start = Rgb::new(230, 100, 100);
+            
+            assert!(snacks.always_overflows(start));
+            assert_eq!(snacks.unsaturated_bounds(start), ([230, 50, 50], [280, 100, 100]));
+            assert_eq!(snacks.reachable_bounds(start), (Rgb::new(230, 50, 50), Rgb::new(255, 100, 100)));
+            
+            // may overflow: only some orders overflow
+            snacks.set(Snack::Pear, 10);
+            assert!(!snacks.always_overflows(start));
+            assert_eq!(snacks.reachable_bounds(start).1.r, 255);
+        }
+        
+        #[test]
+        fn make_meal_any_is_ok() {
+            let finals = [Dye::SootBlack, Dye::SnowWhite, Dye::DesertYellow, Dye::InkBlue];
+            let (dye, meal) = make_meal_any(Dye::BarkBrown, &finals).unwrap();
+            
+            assert_eq!(dye, Dye::SootBlack);
+            assert_eq!(meal, make_meal(Dye::BarkBrown, Dye::SootBlack));
+            assert!(finals.iter().all(|final_dye| make_meal(Dye::BarkBrown, *final_dye).len() >= meal.len()));
+            
+            assert_eq!(make_meal_any(Dye::InkBlue, &finals), Some((Dye::InkBlue, Vec::new())));
+            assert_eq!(make_meal_any(Dye::InkBlue, &[Dye::SnowWhite]), Some((Dye::SnowWhite, make_meal(Dye::InkBlue, Dye::SnowWhite))));
+        }
+        
+        #[test]
+        fn compact_is_ok() {
+            for (starting_dye, final_dye) in [(Dye::SnowWhite, Dye::SootBlack), (Dye::BarkBrown, Dye::MesaRed), (Dye::InkBlue, Dye::InkBlue)] {
+                let snacks = shopping_list(starting_dye, final_dye);
+                let compact = snacks.to_compact();
+                
+                assert_eq!(compact.len(), 8);
+                assert!(compact.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'), "{compact}");
+                assert_eq!(SnackList::from_compact(&compact), Some(snacks));
+            }
+            
+            let mut snacks = SnackList::new();
+            for snack in Snack::VALUES {
+                snacks.set(snack, u8::MAX);
+            }
+            assert_eq!(snacks.to_compact(), "________");
+            assert_eq!(SnackList::from_compact("________"), Some(snacks));
+            
+            for malformed in ["", "AAAAAAA", "AAAAAAAAA", "AAAA+AAA", "AAAA/AAA", "AAAAAAA=", "AAAAAAAé"] {
+                assert_eq!(SnackList::from_compact(malformed), None, "{malformed}");
+            }
+        }
+        
+        #[test]
+        fn menu_with_colors_is_ok() {
+            for (starting_dye, final_dye) in [(Dye::BarkBrown, Dye::MesaRed), (Dye::SnowWhite, Dye::SootBlack), (Dye::InkBlue, Dye::DesertYellow)] {
+                let snacks = shopping_list(starting_dye, final_dye);
+                let menu = make_menu_with_colors(starting_dye, snacks);
+                
+                assert_eq!(menu.iter().map(|(snack, count, _)| (*snack, *count)).collect::<Vec<_>>(), make_menu(starting_dye, snacks));
+                assert_eq!(menu.last().map(|(_, _, color)| *color), apply_meal(starting_dye, &make_meal(starting_dye, final_dye)));
+                assert_eq!(menu.last().map(|(_, _, color)| Dye::try_from(*color).unwrap_or_else(identity)), Some(final_dye));
+                
+                // manual replay
+                let mut color = starting_dye.color();
+                for (snack, count, expected) in menu {
+                    for _ in 0..count {
+                        color = snack.alter(color).unwrap();
+                    }
+                    
+                    assert_eq!(color, expected);
+                }
+            }
+            
+            assert_eq!(make_menu_with_colors(Dye::InkBlue, SnackList::new()), []);
+        }
+        
+        #[test]
+        fn min_total_is_ok() {
+            let mut snacks = SnackList::new();
+            snacks.set(Snack::Pear, 6);
+            snacks.set(Snack::Fruit, 2);
+            snacks.set(Snack::Berries, 1);
+            
+            let menu = make_menu(Dye::BarkBrown, snacks);
+            let min_total = make_menu_min_total(Dye::BarkBrown, snacks);
+            
+            let sum = |menu: &[(Snack, u8)]| menu.iter().map(|(_, count)| u64::from(*count)).sum::<u64>();
+            assert_eq!(sum(&menu), 9);
+            assert_eq!(sum(&min_total), 5);
+            
+            let eat = |menu: Vec<(Snack, u8)>| {
+                let mut rgb = Dye::BarkBrown.color();
+                for (snack, count) in menu {
+                    for _ in 0..count {
+                        rgb = snack.alter(rgb).unwrap();
+                    }
+                }
+                
+                rgb
+            };
+            
+            assert_eq!(eat(menu), eat(min_total));
+            
+            for src in Dye::VALUES {
+                for dst in Dye::VALUES {
+                    let snacks = shopping_list(src, dst);
+                    let min_total = make_menu_min_total(src, snacks);
+                    
+                    assert!(sum(&min_total) <= snacks.sum());
+                    assert_eq!(resulting_dye(src, &min_total.into_iter().flat_map(|(snack, count)| vec![snack; count.into()]).collect::<Vec<_>>()).unwrap_or_else(identity), dst);
+                }
+            }
+        }
+        
+        #[test]
+        fn snacks_for_delta_is_ok() {
+            assert_eq!(snacks_for_delta((0, 0, 0)).map(|snacks| snacks.sum()), Some(0));
+            
+            for snack in Snack::VALUES {
+                let (r, g, b) = snack.effect();
+                let snacks = snacks_for_delta((r.into(), g.into(), b.into())).unwrap();
+                
+                assert_eq!(snacks.get(snack), 1);
+                assert_eq!(snacks.sum(), 1);
+            }
+            
+            for src in Dye::VALUES {
+                for dst in Dye::VALUES {
+                    let (a, b) = (src.color(), dst.color());
+                    let delta = (i32::from(b.r) - i32::from(a.r), i32::from(b.g) - i32::from(a.g), i32::from(b.b) - i32::from(a.b));
+                    
+                    if let Some(snacks) = snacks_for_delta(delta) {
+                        let meal = make_menu(src, snacks).into_iter().flat_map(|(snack, count)| vec![snack; count.into()]).collect::<Vec<_>>();
+                        assert_eq!(apply_meal(src, &meal), Some(b), "{src:?} -> {dst:?}");
+                    }
+                }
+            }
+            
+            assert!(snacks_for_delta((1, 0, 0)).is_none());
+            assert!(snacks_for_delta((5, 0, 0)).is_none());
+            assert!(snacks_for_delta((5, 10, 5)).is_none());
+            assert!(snacks_for_delta((5000, -5000, -5000)).is_none());
+        }
+        
+        #[test]
+        fn from_color_is_ok() {
+            for src in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::CoeurlYellow] {
+                for (dr, dg, db) in [(0, 0, 0), (1, 0, -1), (-2, 1, 1), (3, -3, 2)] {
+                    let Some(starting_color) = src.color().checked_add_signed(dr, dg, db) else { continue };
+                    
+                    for dst in Dye::VALUES {
+                        let meal = make_meal_from_color(starting_color, dst);
+                        let reached = meal.iter().try_fold(starting_color, |color, snack| snack.alter(color)).unwrap();
+                        
+                        assert_eq!(Dye::try_from(reached).unwrap_or_else(identity), dst, "{starting_color:?} -> {dst:?}");
+                    }
+                }
+            }
+        }
+        
+        #[test]
+        fn to_color_is_ok() {
+            for src in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::DesertYellow] {
+                for dst in [Rgb::BLACK, Rgb::WHITE, Rgb::RED, Rgb::new(91, 206, 250), Rgb::new(245, 169, 184)] {
+                    let (meal, reached) = make_meal_to_color(src, dst);
+                    assert_eq!(apply_meal(src, &meal), Some(reached));
+                    
+                    let mut rgb = src.color();
+                    for snack in meal {
+                        assert!(rgb.distance(dst) >= reached.distance(dst), "{src:?} -> {dst:?}");
+                        rgb = snack.alter(rgb).unwrap();
+                    }
+                }
+            }
+            
+            for dye in Dye::VALUES {
+                assert_eq!(Dye::try_from(make_meal_to_color(Dye::DesertYellow, dye.color()).1).unwrap_or_else(identity), dye);
+            }
+        }
+        
+        #[test]
+        fn within_is_ok() {
+            for src in Dye::VALUES {
+                for dst in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::CoeurlYellow] {
+                    let meal = make_meal(src, dst);
+                    assert_eq!(make_meal_within(src, dst, 0), meal);
+                    
+                    for tolerance in [1, Dye::EPSILON, 1000, u32::MAX] {
+                        let within = make_meal_within(src, dst, tolerance);
+                        assert!(within.len() <= meal.len());
+                        assert!(meal.starts_with(&within));
+                        
+                        if within.len() < meal.len() {
+                            assert!(apply_meal(src, &within).unwrap().distance(dst.color()) <= tolerance);
+                        }
+                    }
+                    
+                    assert!(make_meal_within(src, dst, u32::MAX).is_empty());
+                }
+            }
+        }
+        
+        #[test]
+        fn safe_feed_order_is_ok() {
+            let snacks = SnackList::from([Snack::Apple, Snack::Apple, Snack::Plum].as_slice());
+            let starting_color = Rgb::new(250, 128, 128);
+            
+            assert_eq!(apply_meal_from(starting_color, &[Snack::Apple, Snack::Apple, Snack::Plum]), None);
+            
+            let order = safe_feed_order(starting_color, snacks).unwrap().unwrap();
+            assert_eq!(SnackList::from(order.as_slice()), snacks);
+            assert_eq!(apply_meal_from(starting_color, &order), Some(Rgb::new(255, 123, 123)));
+            assert_eq!(safe_feed_order_within(starting_color, snacks, 1), Err(SearchLimitError));
+            
+            for src in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::CoeurlYellow] {
+                for dst in Dye::VALUES {
+                    let snacks = shopping_list(src, dst);
+                    let order = safe_feed_order(src.color(), snacks).unwrap().unwrap();
+                    
+                    assert_eq!(SnackList::from(order.as_slice()), snacks);
+                    assert_eq!(resulting_dye(src, &order).unwrap_or_else(identity), dst);
+                }
+            }
+            
+            assert_eq!(safe_feed_order(Rgb::RED, SnackList::new()), Ok(Some(Vec::new())));
+            assert_eq!(safe_feed_order(Rgb::RED, SnackList::from([Snack::Apple].as_slice())), Ok(None));
+            assert_eq!(safe_feed_order(Rgb::BLACK, SnackList::from([Snack::Plum, Snack::Apple].as_slice())), Ok(None));
+            
+            let mut snacks = SnackList::new();
+            
+            for snack in Snack::VALUES {
+                snacks.set(snack, 200);
+            }
+            
+            let mut overflowing = snacks;
+            overflowing.set(Snack::Apple, 230);
+            
+            assert!(overflowing.always_overflows(Rgb::gray(128)));
+            assert_eq!(safe_feed_order(Rgb::gray(128), overflowing), Ok(None));
+            
+            assert!(!snacks.always_overflows(Rgb::BLACK));
+            assert_eq!(safe_feed_order(Rgb::BLACK, snacks), Ok(None));
+            
+            let mut snacks = SnackList::new();
+            
+            for (snack, count) in [(Snack::Apple, 13), (Snack::Pear, 34), (Snack::Berries, 14), (Snack::Plum, 33), (Snack::Fruit, 56), (Snack::Pineapple, 56)] {
+                snacks.set(snack, count);
+            }
+            
+            // the final color is (251, 251, 255); the last snack must increase blue, as it was at most 255 before,
+            // but Berries, Plum and Fruit all decrease red or green, which would have been 256 before
+            assert!(!snacks.always_overflows(Rgb::new(31, 51, 255)));
+            assert_eq!(safe_feed_order(Rgb::new(31, 51, 255), snacks), Ok(None));
+        }
+        
+        #[test]
+        fn distances_never_increase() {
+            for src in Dye::VALUES {
+                for dst in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::CoeurlYellow, Dye::RolanberryRed] {
+                    let distances = make_meal_distances(src, dst);
+                    let meal = make_meal(src, dst);
+                    
+                    assert!(distances.len() <= meal.len());
+                    assert!(distances.iter().zip(distances.iter().skip(1)).all(|(a, b)| a >= b), "{src:?} -> {dst:?}: {distances:?}");
+                    assert!(distances.first().map_or(true, |d| *d <= src.distance(dst)));
+                    assert_eq!(distances.last().copied().unwrap_or_else(|| src.distance(dst)), apply_meal(src, &meal).unwrap().distance(dst.color()));
+                }
+            }
+        }
+        
+        #[test]
+        fn in_category_is_ok() {
+            for category in Category::VALUES {
+                let mut found = 0;
+                
+                for &src in category.dyes() {
+                    for &dst in category.dyes() {
+                        if let Some(meal) = make_meal_in_category(src, dst, category) {
+                            let mut rgb = src.color();
+                            for snack in &meal {
+                                rgb = snack.alter(rgb).unwrap();
+                                assert_eq!(Dye::try_from(rgb).unwrap_or_else(identity).category(), category, "{src:?} -> {dst:?}");
+                            }
+                            
+                            assert_eq!(Dye::try_from(rgb).unwrap_or_else(identity), dst);
+                            found += 1;
+                        }
+                    }
+                }
+                
+                assert!(found > category.dyes().len(), "{category:?}");
+            }
+            
+            assert!(make_meal_in_category(Dye::AshGrey, Dye::SlateGrey, Category::White).is_some());
+            assert_eq!(make_meal_in_category(Dye::InkBlue, Dye::InkBlue, Category::White), None);
+        }
+        
+        #[test]
+        fn contributions_sum_to_delta() {
+            for src in [Dye::SnowWhite, Dye::SootBlack, Dye::InkBlue, Dye::CoeurlYellow] {
+                for dst in Dye::VALUES {
+                    let meal = make_meal(src, dst);
+                    let (a, b) = (src.color(), apply_meal(src, &meal).unwrap());
+                    
+                    let sum = meal_contributions(&meal).into_iter().fold((0, 0, 0), |acc, c| (acc.0 + c.0, acc.1 + c.1, acc.2 + c.2));
+                    assert_eq!(sum, (i32::from(b.r) - i32::from(a.r), i32::from(b.g) - i32::from(a.g), i32::from(b.b) - i32::from(a.b)));
+                }
+            }
+        }
+        
+        #[test]
+        fn shopping_list_is_ok() {
+            for (src, dst) in [(Dye::SnowWhite, Dye::SootBlack), (Dye::InkBlue, Dye::CoeurlYellow), (Dye::DesertYellow, Dye::DesertYellow)] {
+                let list = shopping_list(src, dst);
+                let menu = make_menu(src, list);
+                
+                assert_eq!(menu.iter().map(|(_, count)| u64::from(*count)).sum::<u64>(), list.sum());
+                
+                let mut rgb = src.color();
+                for (snack, count) in menu {
+                    for _ in 0..count {
+                        rgb = snack.alter(rgb).unwrap();
+                    }
+                }
+                
+                assert_eq!(Dye::try_from(rgb).unwrap_or_else(identity), dst);
+            }
+        }
+        
+        #[test]
+        fn compress_meal_keeps_order() {
+            let meal = make_meal(Dye::BarkBrown, Dye::MesaRed);
+            let menu = make_menu(Dye::BarkBrown, SnackList::from(meal.as_slice()));
+            let compressed = compress_meal(&meal);
+            
+            assert_eq!(compressed, [(Snack::Apple, 4), (Snack::Pear, 1), (Snack::Apple, 1), (Snack::Pear, 1), (Snack::Apple, 1), (Snack::Pear, 1), (Snack::Apple, 1)]);
+            assert_ne!(compressed, menu);
+            
+            assert_eq!(compress_meal(&[Snack::Plum; 300]), [(Snack::Plum, 255), (Snack::Plum, 45)]);
+            assert!(compress_meal(&[]).is_empty());
+        }
+        
+        #[cfg(feature = "fluent")]
+        #[test]
+        fn write_menu_bytes() {
+            let bundle = Lang::English.into_bundle();
+            let mut buf = Vec::new();
+            
+            write_menu(&mut buf, &bundle, Dye::BarkBrown, Dye::MesaRed, shopping_list(Dye::BarkBrown, Dye::MesaRed)).unwrap();
+            assert_eq!(buf, "Required Fruits:\n– \u{2068}7\u{2069} Xelphatol Apples\n– \u{2068}3\u{2069} Mamook Pears\n\nFeed Order:\n– \u{2068}7\u{2069} Xelphatol Apples\n– \u{2068}3\u{2069} Mamook Pears\n".as_bytes());
+            
+            buf.clear();
+            write_menu(&mut buf, &bundle, Dye::InkBlue, Dye::InkBlue, SnackList::new()).unwrap();
+            assert_eq!(buf, b"Required Fruits:\n(none)\n");
+        }
+        
+        #[cfg(all(feature = "fluent", feature = "truecolor"))]
+        #[test]
+        fn ansi_dye_row_is_ok() {
+            use truecolor::ColorSupport;
+            
+            for lang in Lang::VALUES {
+                let bundle = lang.into_bundle();
+                let row = ansi_dye_row_with(Dye::VALUES, &bundle, ColorSupport::None);
+                
+                for dye in Dye::VALUES {
+                    assert!(row.contains(dye.color_name(&bundle)), "{dye:?} not in {row:?}");
+                }
+                
+                assert!(!row.contains('\x1B'));
+            }
+            
+            let bundle = Lang::English.into_bundle();
+            let row = ansi_dye_row_with([Dye::SnowWhite, Dye::SootBlack], &bundle, ColorSupport::TrueColor);
+            
+            assert_eq!(row, format!("{} {}",
+                truecolor::ansi_text_with(ColorSupport::TrueColor, Dye::SnowWhite.color(), "Snow White"),
+                truecolor::ansi_text_with(ColorSupport::TrueColor, Dye::SootBlack.color(), "Soot Black")
+            ));
+            assert!(row.starts_with("\x1B[48;2;"));
+            
+            assert_eq!(ansi_dye_row_with([], &bundle, ColorSupport::None), "");
+            assert_eq!(ansi_dye_row([], &bundle), "");
+        }
+        
+        #[cfg(feature = "fluent")]
+        #[test]
+        fn dye_tree_is_complete() {
+            for lang in Lang::VALUES {
+                let tree = dye_tree(&lang.into_bundle());
+                
+                assert_eq!(tree.len(), 7);
+                assert_eq!(tree.iter().map(|node| node.dyes.len()).sum::<usize>(), 85);
+                
+                for node in tree {
+                    assert!(node.dyes.iter().all(|leaf| leaf.dye.category() == node.category && leaf.color == leaf.dye.color()));
+                }
+            }
+        }
+        
+        #[cfg(feature = "fluent")]
+        #[test]
+        fn legend_is_complete() {
+            let legend = legend(&Lang::English.into_bundle());
+            
+            assert_eq!(legend.len(), Category::VALUES.len());
+            assert_eq!(legend.iter().map(|(_, _, dyes)| dyes.len()).sum::<usize>(), Dye::VALUES.len());
         }
     }
 }
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+use std::convert::identity;
+
+use crate::{Dye, Rgb};
+
+/// Reduces a list of pixels to at most `max_colors` representative colors.
+///
+/// The current implementation is a [median cut](https://en.wikipedia.org/wiki/Median_cut);
+/// the box with the widest color range is repeatedly split at the median of that range,
+/// until there are `max_colors` boxes or every box contains a single color.
+/// Each box is then represented by the average of its pixels.
+///
+/// The returned colors can then be converted to dyes using [`Dye::try_from`](crate::Dye::try_from).
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{median_cut, Rgb};
+///
+/// let pixels = [Rgb::BLACK, Rgb::BLACK, Rgb::gray(10), Rgb::WHITE];
+///
+/// assert_eq!(median_cut(&pixels, 1), [Rgb::gray(66)]);
+/// assert_eq!(median_cut(&pixels, 2), [Rgb::gray(3), Rgb::WHITE]);
+/// ```
+#[must_use]
+pub fn median_cut(pixels: &[Rgb], max_colors: usize) -> Vec<Rgb> {
+    /// A box of unique colors along with their number of occurrences.
+    type Bucket = Vec<(Rgb, u32)>;
+
+    /// Returns the component of `color` designated by `channel`.
+    const fn component(color: Rgb, channel: usize) -> u8 {
+        match channel {
+            0 => color.r,
+            1 => color.g,
+            _ => color.b
+        }
+    }
+
+    /// Returns the channel with the widest range in `colors`, along with the width of that range.
+    fn widest_channel(colors: &Bucket) -> (usize, u8) {
+        (0..3).map(|channel| {
+            let min = colors.iter().map(|(color, _)| component(*color, channel)).min().unwrap_or(0);
+            let max = colors.iter().map(|(color, _)| component(*color, channel)).max().unwrap_or(0);
+
+            (channel, max - min)
+        }).max_by_key(|(_, range)| *range).unwrap()
+    }
+
+    /// Returns the average color of `colors`, rounded to the nearest integer.
+    #[allow(clippy::cast_possible_truncation)]
+    fn average(colors: &Bucket) -> Rgb {
+        let n: u64 = colors.iter().map(|(_, count)| u64::from(*count)).sum();
+        let sum = |channel| colors.iter().map(|(color, count)| u64::from(component(*color, channel)) * u64::from(*count)).sum::<u64>();
+
+        Rgb::new(
+            ((sum(0) + n / 2) / n) as u8,
+            ((sum(1) + n / 2) / n) as u8,
+            ((sum(2) + n / 2) / n) as u8
+        )
+    }
+
+    if pixels.is_empty() || max_colors == 0 {
+        return Vec::new();
+    }
+
+    // deduplicate the pixels
+    let mut sorted = pixels.to_vec();
+    sorted.sort_unstable_by_key(|color| u32::from(*color));
+
+    let mut unique: Bucket = Vec::new();
+    for color in sorted {
+        match unique.last_mut() {
+            Some((last, count)) if *last == color => *count += 1,
+            _ => unique.push((color, 1))
+        }
+    }
+
+    let mut boxes = vec![unique];
+
+    while boxes.len() < max_colors {
+        // split the box having the widest range
+        let Some((i, (channel, _))) = boxes.iter()
+            .map(widest_channel)
+            .enumerate()
+            .filter(|(_, (_, range))| *range > 0)
+            .max_by_key(|(_, (_, range))| *range)
+        else {
+            break;
+        };
+
+        let colors = &mut boxes[i];
+        colors.sort_unstable_by_key(|(color, _)| component(*color, channel));
+
+        // find the median pixel, while leaving at least one color on each side
+        let total: u64 = colors.iter().map(|(_, count)| u64::from(*count)).sum();
+
+        let mut acc = 0;
+        let median = colors.iter()
+            .position(|(_, count)| {
+                acc += u64::from(*count);
+                acc * 2 > total
+            })
+            .map_or(1, |j| j + 1)
+            .clamp(1, colors.len() - 1);
+
+        let upper = colors.split_off(median);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(average).collect()
+}
+
+/// Returns the set of dyes closest to each of the pixels.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, Rgb, unique_dyes};
+///
+/// let pixels = [Dye::InkBlue.color(), Rgb::new(155, 179, 98), Dye::AppleGreen.color()];
+/// let dyes = unique_dyes(&pixels);
+///
+/// assert_eq!(dyes.len(), 2);
+/// assert!(dyes.contains(&Dye::InkBlue) && dyes.contains(&Dye::AppleGreen));
+/// ```
+#[must_use]
+pub fn unique_dyes(pixels: &[Rgb]) -> HashSet<Dye> {
+    pixels.iter().map(|pixel| Dye::try_from(*pixel).unwrap_or_else(identity)).collect()
+}
+
+/// Creates a gradient of `steps` colors going through all the `dyes`, in order.
+///
+/// The stops are evenly spaced along the gradient, which is linearly interpolated between each pair of successive dyes;
+/// the first and last stops are always the colors of the first and last dyes.
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Dye, gradient_stops, Rgb};
+///
+/// assert_eq!(gradient_stops(&[Dye::SnowWhite, Dye::SootBlack], 2), [Dye::SnowWhite.color(), Dye::SootBlack.color()]);
+///
+/// let stops = gradient_stops(&[Dye::SnowWhite, Dye::DalamudRed, Dye::InkBlue], 5);
+/// assert_eq!(stops[2], Dye::DalamudRed.color());
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn gradient_stops(dyes: &[Dye], steps: usize) -> Vec<Rgb> {
+    match (dyes, steps) {
+        ([], _) | (_, 0) => Vec::new(),
+        ([dye, ..], 1) | ([dye], _) => vec![dye.color(); steps],
+        _ => {
+            let segments = dyes.len() - 1;
+
+            (0..steps).map(|i| {
+                // position of the stop along the whole gradient, between `0` and `segments`
+                let position = (i * segments) as f32 / (steps - 1) as f32;
+                let segment = (position as usize).min(segments - 1);
+                let t = position - segment as f32;
+
+                dyes[segment].color().lerp(dyes[segment + 1].color(), t)
+            }).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn median_cut_bounds() {
+        let pixels = [Rgb::RED, Rgb::RED, Rgb::GREEN, Rgb::BLUE, Rgb::gray(30), Rgb::RED];
+
+        assert_eq!(median_cut(&pixels, 1), [Rgb::new(133, 48, 48)]);
+        assert!(median_cut(&[], 3).is_empty());
+        assert!(median_cut(&pixels, 0).is_empty());
+
+        let mut all = median_cut(&pixels, 16);
+        all.sort_unstable_by_key(|color| u32::from(*color));
+        assert_eq!(all, [Rgb::BLUE, Rgb::GREEN, Rgb::gray(30), Rgb::RED]);
+    }
+
+    #[test]
+    fn gradient_stops_bounds() {
+        let (a, b, c) = (Dye::SnowWhite, Dye::CurrantPurple, Dye::MossGreen);
+
+        assert_eq!(gradient_stops(&[a, b], 2), [a.color(), b.color()]);
+        assert_eq!(gradient_stops(&[a, b, c], 3), [a.color(), b.color(), c.color()]);
+        assert_eq!(gradient_stops(&[a, b], 1), [a.color()]);
+        assert_eq!(gradient_stops(&[c], 3), [c.color(); 3]);
+        assert!(gradient_stops(&[], 3).is_empty());
+        assert!(gradient_stops(&[a, b], 0).is_empty());
+
+        let stops = gradient_stops(&[Dye::SnowWhite, Dye::SootBlack], 100);
+        assert_eq!(stops.len(), 100);
+        assert_eq!(stops.last(), Some(&Dye::SootBlack.color()));
+        assert!(stops.iter().zip(&stops[1..]).all(|(a, b)| a.luma() >= b.luma()));
+    }
+
+    #[test]
+    fn unique_dyes_of_two() {
+        let (a, b) = (Dye::CoeurlYellow.color(), Dye::ShadowBlue.color());
+        let pixels: Vec<Rgb> = (0..64).map(|i| if i % 3 == 0 { a } else { b }).collect();
+
+        assert_eq!(unique_dyes(&pixels), HashSet::from([Dye::CoeurlYellow, Dye::ShadowBlue]));
+        assert!(unique_dyes(&[]).is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use ::ratatui::style::Color;
+
+use crate::{Dye, Rgb};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ratatui")))]
+impl From<Rgb> for Color {
+    /// Converts this color to a [`Color::Rgb`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    /// use ratatui::style::Color;
+    ///
+    /// assert_eq!(Color::from(Rgb::new(91, 206, 250)), Color::Rgb(91, 206, 250));
+    /// ```
+    #[inline]
+    fn from(value: Rgb) -> Color {
+        Color::Rgb(value.r, value.g, value.b)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ratatui")))]
+impl From<Dye> for Color {
+    /// Converts the color of this dye to a [`Color::Rgb`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Dye;
+    /// use ratatui::style::Color;
+    ///
+    /// assert_eq!(Color::from(Dye::SnowWhite), Color::from(Dye::SnowWhite.color()));
+    /// ```
+    #[inline]
+    fn from(value: Dye) -> Color {
+        Color::from(value.color())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn color() {
+        for dye in Dye::VALUES {
+            let color = dye.color();
+
+            assert_eq!(Color::from(dye), Color::Rgb(color.r, color.g, color.b));
+            assert_eq!(Color::from(color), Color::from(dye));
+        }
+    }
+}
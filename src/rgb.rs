@@ -73,25 +73,60 @@ impl Rgb {
         Rgb { r: rgb, g: rgb, b: rgb }
     }
 
-    /// Parses a hex color.
+    /// Returns the color of a [black body](https://en.wikipedia.org/wiki/Black-body_radiation) at the specified temperature,
+    /// in kelvins. The temperature is clamped between `1000` and `40000`.
+    ///
+    /// This uses Tanner Helland's approximation, which is accurate enough for matching a light's warmth.
     ///
     /// # Examples
     ///
     /// ```
     /// use chocodye::Rgb;
     ///
+    /// assert_eq!(Rgb::from_kelvin(1000.0), Rgb::new(255, 68, 0)); // candle
+    /// assert_eq!(Rgb::from_kelvin(6600.0), Rgb::WHITE); // daylight
+    /// assert_eq!(Rgb::from_kelvin(15000.0), Rgb::new(181, 205, 255)); // clear blue sky
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::suboptimal_flops)]
+    pub fn from_kelvin(kelvin: f32) -> Rgb {
+        let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+
+        let r = if t <= 66.0 { 255.0 } else { 329.698_73 * (t - 60.0).powf(-0.133_204_76) };
+        let g = if t <= 66.0 { 99.470_8 * t.ln() - 161.119_57 } else { 288.122_17 * (t - 60.0).powf(-0.075_514_85) };
+        let b = if t >= 66.0 { 255.0 } else if t <= 19.0 { 0.0 } else { 138.517_73 * (t - 10.0).ln() - 305.044_8 };
+
+        let component = |c: f32| c.clamp(0.0, 255.0).round() as u8;
+        Rgb { r: component(r), g: component(g), b: component(b) }
+    }
+
+    /// Parses a hex color, either `#rrggbb` or its shorthand `#rgb`, whose digits are doubled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{ParseHexError, Rgb};
+    ///
     /// assert_eq!(Rgb::from_hex("#ffffff"), Ok(Rgb::new(255, 255, 255)));
-    /// assert!(Rgb::from_hex("#fff").is_err());
+    /// assert_eq!(Rgb::from_hex("#f0a"), Ok(Rgb::new(255, 0, 170)));
+    /// assert_eq!(Rgb::from_hex("#ffff"), Err(ParseHexError::BadLen));
     /// assert!(Rgb::from_hex("ffffff").is_err());
+    /// assert_eq!(Rgb::from_hex("#ff0g00"), Err(ParseHexError::NonHexDigit { pos: 4 }));
     /// ```
     #[allow(clippy::missing_errors_doc)]
     pub fn from_hex(s: &str) -> Result<Rgb, ParseHexError> {
-        if s.len() != 7 {
+        if s.len() != 7 && s.len() != 4 {
             Err(ParseHexError::BadLen)
         }
         else if s.as_bytes()[0] != b'#' {
             Err(ParseHexError::MissingHash)
         }
+        else if let Some(pos) = s.bytes().skip(1).position(|b| !b.is_ascii_hexdigit()) {
+            Err(ParseHexError::NonHexDigit { pos: pos + 1 })
+        }
+        else if s.len() == 4 {
+            Ok(Rgb::from_rgb444(u16::from_str_radix(&s[1..4], 16)?))
+        }
         else {
             Ok((u32::from_str_radix(&s[1..7], 16)? << 8).into())
         }
@@ -149,9 +184,310 @@ impl Rgb {
         (dx * dx) as u32 + (dy * dy) as u32 + (dz * dz) as u32
     }
 
+    /// Returns the average of `colors`, component by component, or `None` if `colors` is empty.
+    ///
+    /// Each component is the arithmetic mean of the corresponding components of `colors`, rounded to the nearest integer,
+    /// halves being rounded up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::mix(&[Rgb::BLACK, Rgb::WHITE]), Some(Rgb::gray(128)));
+    /// assert_eq!(Rgb::mix(&[Rgb::RED]), Some(Rgb::RED));
+    /// assert_eq!(Rgb::mix(&[]), None);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn mix(colors: &[Rgb]) -> Option<Rgb> {
+        if colors.is_empty() {
+            return None;
+        }
+
+        let n = colors.len() as u64;
+        let sum = |component: fn(&Rgb) -> u8| colors.iter().map(|color| u64::from(component(color))).sum::<u64>();
+        let average = |component| ((sum(component) + n / 2) / n) as u8;
+
+        Some(Rgb::new(average(|c| c.r), average(|c| c.g), average(|c| c.b)))
+    }
+
+    /// Computes the [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) between `self` and `other`,
+    /// that is the sum of the absolute differences of their components.
+    ///
+    /// This is cheaper than [`Rgb::distance`], but does not order colors exactly the same way:
+    /// it is only intended for speed-sensitive approximate matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::WHITE.manhattan_distance(Rgb::BLACK), 765);
+    /// assert_eq!(Rgb::new(10, 20, 30).manhattan_distance(Rgb::new(20, 10, 30)), 20);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn manhattan_distance(self, other: Rgb) -> u32 {
+        self.r.abs_diff(other.r) as u32 + self.g.abs_diff(other.g) as u32 + self.b.abs_diff(other.b) as u32
+    }
+
+    /// Computes the signed difference between `self` and `other` for each component, `self - other`.
+    ///
+    /// Unlike [`Rgb::distance`], this keeps the direction of the difference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// let a = Rgb::new(200, 10, 50);
+    /// let b = Rgb::new(100, 60, 50);
+    ///
+    /// assert_eq!(a.delta(b), (100, -50, 0));
+    /// assert_eq!(b.delta(a), (-100, 50, 0));
+    ///
+    /// // `a.delta(b) == -b.delta(a)`
+    /// let (dr, dg, db) = b.delta(a);
+    /// assert_eq!(a.delta(b), (-dr, -dg, -db));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn delta(self, other: Rgb) -> (i16, i16, i16) {
+        (
+            (self.r as i16) - (other.r as i16),
+            (self.g as i16) - (other.g as i16),
+            (self.b as i16) - (other.b as i16)
+        )
+    }
+
+    /// Linearly interpolates between `self` and `other`, rounding each component to the nearest integer.
+    ///
+    /// `t` is clamped between `0.0` and `1.0`; `self.lerp(other, 0.0) == self` and `self.lerp(other, 1.0) == other`.
+    /// Halfway components are rounded away from zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::RED.lerp(Rgb::BLUE, 0.0), Rgb::RED);
+    /// assert_eq!(Rgb::RED.lerp(Rgb::BLUE, 1.0), Rgb::BLUE);
+    /// assert_eq!(Rgb::BLACK.lerp(Rgb::WHITE, 0.5), Rgb::gray(128));
+    /// assert_eq!(Rgb::BLACK.lerp(Rgb::WHITE, 2.0), Rgb::WHITE);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::suboptimal_flops)]
+    pub fn lerp(self, other: Rgb, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+
+        Rgb { r: lerp(self.r, other.r), g: lerp(self.g, other.g), b: lerp(self.b, other.b) }
+    }
+
+    /// Returns the point of the segment `[a, b]` that is the closest to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(10, 20, 30).project_onto_segment(Rgb::BLACK, Rgb::WHITE), Rgb::gray(20));
+    /// assert_eq!(Rgb::WHITE.project_onto_segment(Rgb::BLACK, Rgb::gray(100)), Rgb::gray(100)); // clamped
+    ///
+    /// assert_eq!(Rgb::RED.project_onto_segment(Rgb::RED, Rgb::BLUE), Rgb::RED);
+    /// assert_eq!(Rgb::BLUE.project_onto_segment(Rgb::RED, Rgb::BLUE), Rgb::BLUE);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::suboptimal_flops)]
+    pub fn project_onto_segment(self, a: Rgb, b: Rgb) -> Rgb {
+        let ab = [f32::from(b.r) - f32::from(a.r), f32::from(b.g) - f32::from(a.g), f32::from(b.b) - f32::from(a.b)];
+        let ap = [f32::from(self.r) - f32::from(a.r), f32::from(self.g) - f32::from(a.g), f32::from(self.b) - f32::from(a.b)];
+
+        let len = ab[0] * ab[0] + ab[1] * ab[1] + ab[2] * ab[2];
+        if len == 0.0 {
+            return a;
+        }
+
+        let t = ((ap[0] * ab[0] + ap[1] * ab[1] + ap[2] * ab[2]) / len).clamp(0.0, 1.0);
+
+        Rgb {
+            r: (f32::from(a.r) + t * ab[0]).round() as u8,
+            g: (f32::from(a.g) + t * ab[1]).round() as u8,
+            b: (f32::from(a.b) + t * ab[2]).round() as u8
+        }
+    }
+
+    /// Reorders the components of `self`; `order[i]` is the index of the component put at index `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is greater than `2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(1, 2, 3).swizzle([2, 1, 0]), Rgb::new(3, 2, 1)); // BGR
+    /// assert_eq!(Rgb::new(1, 2, 3).swizzle([0, 0, 0]), Rgb::gray(1));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn swizzle(self, order: [usize; 3]) -> Rgb {
+        let components = [self.r, self.g, self.b];
+
+        Rgb {
+            r: components[order[0]],
+            g: components[order[1]],
+            b: components[order[2]]
+        }
+    }
+
+    /// Rotates the components of `self` to the left, RGB becoming GBR.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(1, 2, 3).rotate_channels(), Rgb::new(2, 3, 1));
+    /// assert_eq!(Rgb::RED.rotate_channels(), Rgb::BLUE);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn rotate_channels(self) -> Rgb {
+        self.swizzle([1, 2, 0])
+    }
+
+    /// Returns the components of `self` followed by `alpha`, the byte layout of RGBA8 image buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(91, 206, 250).to_rgba_bytes(255), [91, 206, 250, 255]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_rgba_bytes(self, alpha: u8) -> [u8; 4] {
+        [self.r, self.g, self.b, alpha]
+    }
+
+    /// Packs `self` into 15 bits, five bits per component, as `0b0rrrrrgggggbbbbb`.
+    ///
+    /// Each component is rounded to the nearest of 32 levels; [`Rgb::from_rgb555`] restores it within `4` units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::WHITE.to_rgb555(), 0x7fff);
+    /// assert_eq!(Rgb::RED.to_rgb555(), 0x7c00);
+    /// assert_eq!(Rgb::from_rgb555(Rgb::new(91, 206, 250).to_rgb555()), Rgb::new(90, 206, 247));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_rgb555(self) -> u16 {
+        const fn quantize(c: u8) -> u16 {
+            (c as u16 * 31 + 127) / 255
+        }
+
+        quantize(self.r) << 10 | quantize(self.g) << 5 | quantize(self.b)
+    }
+
+    /// Unpacks a color packed by [`Rgb::to_rgb555`]. The most significant bit is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from_rgb555(0x7fff), Rgb::WHITE);
+    /// assert_eq!(Rgb::from_rgb555(0x001f), Rgb::BLUE);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_rgb555(rgb: u16) -> Rgb {
+        const fn expand(c: u16) -> u8 {
+            (((c & 0x1f) * 255 + 15) / 31) as u8
+        }
+
+        Rgb { r: expand(rgb >> 10), g: expand(rgb >> 5), b: expand(rgb) }
+    }
+
+    /// Packs `self` into 12 bits, four bits per component, as `0b0000rrrrggggbbbb`.
+    ///
+    /// Each component is rounded to the nearest of 16 levels; [`Rgb::from_rgb444`] restores it within `8` units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::WHITE.to_rgb444(), 0xfff);
+    /// assert_eq!(Rgb::GREEN.to_rgb444(), 0x0f0);
+    /// assert_eq!(Rgb::from_rgb444(Rgb::new(91, 206, 250).to_rgb444()), Rgb::new(85, 204, 255));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_rgb444(self) -> u16 {
+        const fn quantize(c: u8) -> u16 {
+            (c as u16 * 15 + 127) / 255
+        }
+
+        quantize(self.r) << 8 | quantize(self.g) << 4 | quantize(self.b)
+    }
+
+    /// Unpacks a color packed by [`Rgb::to_rgb444`]. The four most significant bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from_rgb444(0xfff), Rgb::WHITE);
+    /// assert_eq!(Rgb::from_rgb444(0xf00), Rgb::RED);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_rgb444(rgb: u16) -> Rgb {
+        const fn expand(c: u16) -> u8 {
+            ((c & 0xf) * 17) as u8
+        }
+
+        Rgb { r: expand(rgb >> 8), g: expand(rgb >> 4), b: expand(rgb) }
+    }
+
+    /// Computes the unrounded [luma](https://en.wikipedia.org/wiki/Luma_(video)), the brightness of `self`, between `0.0` and `255.0`.
+    ///
+    /// This is the Rec. 601 luma, `0.299 * r + 0.587 * g + 0.114 * b`, computed on `f32`s from the gamma-compressed components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::BLACK.luma_f32(), 0.0);
+    /// assert!((Rgb::WHITE.luma_f32() - 255.0).abs() < 1e-4);
+    /// assert!((Rgb::new(10, 20, 30).luma_f32() - 18.15).abs() < 1e-4);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn luma_f32(self) -> f32 {
+        0.299 * (self.r as f32) + 0.587 * (self.g as f32) + 0.114 * (self.b as f32)
+    }
+
     /// Computes the [luma](https://en.wikipedia.org/wiki/Luma_(video)), the brightness of `self`.
     /// Takes human perception into account. Useful for sorting colors.
     ///
+    /// This is [`Rgb::luma_f32`] rounded to the nearest integer.
+    ///
     /// # Examples
     ///
     /// ```
@@ -167,9 +503,51 @@ impl Rgb {
     /// ```
     #[must_use]
     #[inline]
-    #[allow(clippy::cast_possible_truncation, clippy::suboptimal_flops)]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     pub fn luma(self) -> u8 {
-        (0.299 * (self.r as f32) + 0.587 * (self.g as f32) + 0.114 * (self.b as f32)) as u8
+        self.luma_f32().round() as u8
+    }
+
+    /// Returns `true` if `self` is a dark color, that is if its [luma](Rgb::luma) is below `128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert!(Rgb::BLACK.is_dark());
+    /// assert!(Rgb::BLUE.is_dark());
+    /// assert!(Rgb::gray(127).is_dark());
+    ///
+    /// assert!(!Rgb::gray(128).is_dark());
+    /// assert!(!Rgb::GREEN.is_dark());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_dark(self) -> bool {
+        self.luma() < 128
+    }
+
+    /// Returns `255` minus the [luma](Rgb::luma) of `self`, computed with integers so that it can be used in constants.
+    ///
+    /// Sorting by this key orders the colors from the brightest to the darkest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::WHITE.luma_sort_key(), 0);
+    /// assert_eq!(Rgb::BLACK.luma_sort_key(), 255);
+    /// assert_eq!(Rgb::new(10, 20, 30).luma_sort_key(), 255 - Rgb::new(10, 20, 30).luma());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn luma_sort_key(self) -> u8 {
+        let luma = (299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32 + 500) / 1000;
+
+        u8::MAX - luma as u8
     }
 
     /// Converts this color into a gray shade. Takes human perception into account.
@@ -188,10 +566,35 @@ impl Rgb {
     pub fn grayscale(self) -> Rgb {
         Rgb::gray(self.luma())
     }
+
+    /// Rounds each component of `self` to the nearest multiple of `51`, giving the closest color
+    /// of the 216-color [web-safe palette](https://en.wikipedia.org/wiki/Web_colors#Web-safe_colors).
+    ///
+    /// This is lossy, and only intended for displays supporting few colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(91, 206, 250).to_web_safe(), Rgb::new(102, 204, 255));
+    /// assert_eq!(Rgb::RED.to_web_safe(), Rgb::RED);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_web_safe(self) -> Rgb {
+        const fn round(c: u8) -> u8 {
+            ((c as u16 + 25) / 51 * 51) as u8
+        }
+
+        Rgb::new(round(self.r), round(self.g), round(self.b))
+    }
 }
 
 impl From<u32> for Rgb {
-    /// Converts an `u32` in `RRGGBBAA` format to its corresponding color. The alpha bits are ignored.
+    /// Converts an `u32` in `RRGGBBAA` format to its corresponding color. The alpha bits are ignored;
+    /// use [`Rgba::from_u32`](crate::Rgba::from_u32) to keep them.
     ///
     /// # Examples
     ///
@@ -227,6 +630,70 @@ impl From<Rgb> for u32 {
     }
 }
 
+impl From<[u8; 3]> for Rgb {
+    /// Converts an array of `[r, g, b]` components to its corresponding color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from([1, 2, 3]), Rgb::new(1, 2, 3));
+    /// ```
+    #[inline]
+    fn from([r, g, b]: [u8; 3]) -> Rgb {
+        Rgb::new(r, g, b)
+    }
+}
+
+impl From<Rgb> for [u8; 3] {
+    /// Converts this color to an array of `[r, g, b]` components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(<[u8; 3]>::from(Rgb::new(1, 2, 3)), [1, 2, 3]);
+    /// ```
+    #[inline]
+    fn from(value: Rgb) -> [u8; 3] {
+        [value.r, value.g, value.b]
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    /// Converts a tuple of `(r, g, b)` components to its corresponding color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::from((1, 2, 3)), Rgb::new(1, 2, 3));
+    /// ```
+    #[inline]
+    fn from((r, g, b): (u8, u8, u8)) -> Rgb {
+        Rgb::new(r, g, b)
+    }
+}
+
+impl From<Rgb> for (u8, u8, u8) {
+    /// Converts this color to a tuple of `(r, g, b)` components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(<(u8, u8, u8)>::from(Rgb::new(1, 2, 3)), (1, 2, 3));
+    /// ```
+    #[inline]
+    fn from(value: Rgb) -> (u8, u8, u8) {
+        (value.r, value.g, value.b)
+    }
+}
+
 impl Default for Rgb {
     /// The default color is arbitrarily set to `#5bcefa`, a light blue.
     #[inline]
@@ -245,6 +712,22 @@ impl fmt::Debug for Rgb {
     }
 }
 
+impl fmt::Display for Rgb {
+    /// Formats `self` as a lowercase hex color, like [`LowerHex`](fmt::LowerHex).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(91, 206, 250).to_string(), "#5bcefa");
+    /// assert_eq!(format!("{}", Rgb::RED), format!("{:x}", Rgb::RED));
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
 impl fmt::LowerHex for Rgb {
     /// Formats `self` as a hex color.
     ///
@@ -280,14 +763,20 @@ impl fmt::UpperHex for Rgb {
 /// This error is used as the error type for the [`Rgb::from_hex`] function.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ParseHexError {
-    /// The string length is not seven (`#rrggbb`).
+    /// The string length is neither seven (`#rrggbb`) nor four (`#rgb`).
     BadLen,
 
     /// The string does not begin with a hashtag (`#`).
     MissingHash,
 
     /// The string contains an invalid digit.
-    BadInt(ParseIntError)
+    BadInt(ParseIntError),
+
+    /// The byte at `pos` is not an ASCII hexadecimal digit.
+    NonHexDigit {
+        /// The byte index of the invalid character in the string.
+        pos: usize
+    }
 }
 
 impl fmt::Display for ParseHexError {
@@ -295,7 +784,8 @@ impl fmt::Display for ParseHexError {
         match self {
             ParseHexError::BadLen => write!(f, "bad length"),
             ParseHexError::MissingHash => write!(f, "missing `#` prefix"),
-            ParseHexError::BadInt(e) => fmt::Display::fmt(e, f)
+            ParseHexError::BadInt(e) => fmt::Display::fmt(e, f),
+            ParseHexError::NonHexDigit { pos } => write!(f, "invalid hex digit at position {pos}")
         }
     }
 }
@@ -314,3 +804,127 @@ impl Error for ParseHexError {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn swizzle() {
+        let color = Rgb::new(91, 206, 250);
+
+        assert_eq!(color.swizzle([0, 1, 2]), color);
+        assert_eq!(color.swizzle([2, 1, 0]), Rgb::new(250, 206, 91));
+        assert_eq!(color.swizzle([2, 1, 0]).swizzle([2, 1, 0]), color);
+
+        assert_ne!(color.rotate_channels(), color);
+        assert_ne!(color.rotate_channels().rotate_channels(), color);
+        assert_eq!(color.rotate_channels().rotate_channels().rotate_channels(), color);
+    }
+
+    #[test]
+    fn from_kelvin() {
+        let colors: Vec<Rgb> = (10..=400).map(|k| Rgb::from_kelvin(k as f32 * 100.0)).collect();
+
+        for (warm, cold) in colors.iter().zip(&colors[1..]) {
+            assert!(cold.r <= warm.r, "{warm:?} {cold:?}");
+            assert!(cold.b >= warm.b, "{warm:?} {cold:?}");
+        }
+
+        assert_eq!(Rgb::from_kelvin(0.0), Rgb::from_kelvin(1000.0));
+        assert_eq!(Rgb::from_kelvin(f32::INFINITY), Rgb::from_kelvin(40000.0));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        for c in 0..=u8::MAX {
+            for color in [Rgb::gray(c), Rgb::new(c, u8::MAX - c, c / 2)] {
+                let rgb555 = Rgb::from_rgb555(color.to_rgb555());
+                let rgb444 = Rgb::from_rgb444(color.to_rgb444());
+
+                for (a, b, c) in [(color.r, rgb555.r, rgb444.r), (color.g, rgb555.g, rgb444.g), (color.b, rgb555.b, rgb444.b)] {
+                    assert!(a.abs_diff(b) <= 4, "{color:?} -> {rgb555:?}");
+                    assert!(a.abs_diff(c) <= 8, "{color:?} -> {rgb444:?}");
+                }
+            }
+        }
+
+        for rgb555 in 0..0x8000 {
+            assert_eq!(Rgb::from_rgb555(rgb555).to_rgb555(), rgb555);
+        }
+
+        for rgb444 in 0..0x1000 {
+            assert_eq!(Rgb::from_rgb444(rgb444).to_rgb444(), rgb444);
+        }
+    }
+
+    #[test]
+    fn from_hex() {
+        assert_eq!(Rgb::from_hex("#5bcefa"), Ok(Rgb::new(91, 206, 250)));
+        assert_eq!(Rgb::from_hex("#5BCEFA"), Ok(Rgb::new(91, 206, 250)));
+        assert_eq!(Rgb::from_hex("#abc"), Rgb::from_hex("#aabbcc"));
+        assert_eq!(Rgb::from_hex("#000"), Ok(Rgb::BLACK));
+
+        assert_eq!(Rgb::from_hex("fff0"), Err(ParseHexError::MissingHash));
+        assert_eq!(Rgb::from_hex("#ff"), Err(ParseHexError::BadLen));
+        assert_eq!(Rgb::from_hex("#fé"), Err(ParseHexError::NonHexDigit { pos: 2 }));
+
+        // multibyte characters must not be sliced through
+        assert_eq!("#ffé00".len(), 7);
+        assert_eq!(Rgb::from_hex("#ffé00"), Err(ParseHexError::NonHexDigit { pos: 3 }));
+        assert_eq!("#\u{e9}eeee".len(), 7);
+        assert_eq!(Rgb::from_hex("#\u{e9}eeee"), Err(ParseHexError::NonHexDigit { pos: 1 }));
+        assert_eq!(Rgb::from_hex("#eeeeé"), Err(ParseHexError::NonHexDigit { pos: 5 }));
+        assert_eq!(Rgb::from_hex("#+fffff"), Err(ParseHexError::NonHexDigit { pos: 1 }));
+        assert_eq!(Rgb::from_hex("#fffff "), Err(ParseHexError::NonHexDigit { pos: 6 }));
+    }
+
+    #[test]
+    fn lerp() {
+        assert_eq!(Rgb::WHITE.lerp(Rgb::BLACK, 0.5), Rgb::gray(128));
+        assert_eq!(Rgb::BLACK.lerp(Rgb::WHITE, 0.5), Rgb::gray(128));
+
+        let (a, b) = (Rgb::new(91, 206, 250), Rgb::new(245, 169, 184));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, -1.0), a);
+        assert_eq!(a.lerp(b, f32::INFINITY), b);
+    }
+
+    #[test]
+    fn luma_sort_key() {
+        for r in (0..=u8::MAX).step_by(3) {
+            for g in (0..=u8::MAX).step_by(3) {
+                for b in (0..=u8::MAX).step_by(3) {
+                    let color = Rgb::new(r, g, b);
+                    assert!(color.luma_sort_key().abs_diff(u8::MAX - color.luma()) <= 1, "{color:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        for (a, b) in [(Rgb::RED, Rgb::BLUE), (Rgb::new(91, 206, 250), Rgb::new(245, 169, 184)), (Rgb::gray(7), Rgb::gray(7)), (Rgb::new(0, 255, 3), Rgb::new(255, 0, 1))] {
+            let expected = (i32::from(a.r) - i32::from(b.r)).unsigned_abs()
+                + (i32::from(a.g) - i32::from(b.g)).unsigned_abs()
+                + (i32::from(a.b) - i32::from(b.b)).unsigned_abs();
+
+            assert_eq!(a.manhattan_distance(b), expected);
+            assert_eq!(b.manhattan_distance(a), expected);
+        }
+
+        // the orderings differ: `a` is closer to black than `b` by the L1 metric, but farther by the squared L2 metric
+        let (a, b) = (Rgb::new(60, 0, 0), Rgb::gray(25));
+        assert!(a.manhattan_distance(Rgb::BLACK) < b.manhattan_distance(Rgb::BLACK));
+        assert!(a.distance(Rgb::BLACK) > b.distance(Rgb::BLACK));
+    }
+
+    #[test]
+    fn mix() {
+        assert_eq!(Rgb::mix(&[Rgb::new(10, 20, 30), Rgb::new(40, 50, 60), Rgb::new(70, 80, 91)]), Some(Rgb::new(40, 50, 60)));
+        assert_eq!(Rgb::mix(&[Rgb::RED, Rgb::GREEN, Rgb::BLUE]), Some(Rgb::gray(85)));
+        assert_eq!(Rgb::mix(&[Rgb::gray(1), Rgb::gray(2), Rgb::gray(2)]), Some(Rgb::gray(2)));
+        assert_eq!(Rgb::mix(&[Rgb::WHITE; 1000]), Some(Rgb::WHITE));
+    }
+}
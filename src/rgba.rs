@@ -0,0 +1,147 @@
+use std::fmt::{self, Formatter};
+
+use crate::Rgb;
+
+/// A color represented by three `u8` components, along with an `u8` alpha channel.
+///
+/// Unlike [`Rgb`], this struct preserves the alpha bits when converted from and to an `u32` in `RRGGBBAA` format.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rgba {
+    /// The red component.
+    pub r: u8,
+
+    /// The green component.
+    pub g: u8,
+
+    /// The blue component.
+    pub b: u8,
+
+    /// The alpha component, from `0` for transparent to `255` for opaque.
+    pub a: u8
+}
+
+impl Rgba {
+    /// Creates a new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgba;
+    ///
+    /// assert_eq!(Rgba::new(5, 7, 11, 13), Rgba { r: 5, g: 7, b: 11, a: 13 });
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+
+    /// Converts an `u32` in `RRGGBBAA` format to its corresponding color, keeping the alpha bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgba;
+    ///
+    /// assert_eq!(Rgba::from_u32(0x0102037F), Rgba::new(1, 2, 3, 127));
+    /// assert_eq!(u32::from(Rgba::from_u32(0x0ABCDEF0)), 0x0ABCDEF0); // The alpha bits are kept.
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_u32(value: u32) -> Rgba {
+        let [r, g, b, a] = value.to_be_bytes();
+
+        Rgba { r, g, b, a }
+    }
+
+    /// Returns the color of `self`, without its alpha channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Rgb, Rgba};
+    ///
+    /// assert_eq!(Rgba::new(1, 2, 3, 4).rgb(), Rgb::new(1, 2, 3));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn rgb(self) -> Rgb {
+        Rgb::new(self.r, self.g, self.b)
+    }
+}
+
+impl From<u32> for Rgba {
+    /// Converts an `u32` in `RRGGBBAA` format to its corresponding color, as [`Rgba::from_u32`].
+    #[inline]
+    fn from(value: u32) -> Rgba {
+        Rgba::from_u32(value)
+    }
+}
+
+impl From<Rgba> for u32 {
+    /// Converts this color to an `u32` in `RRGGBBAA` format, keeping the alpha bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Rgba;
+    ///
+    /// assert_eq!(u32::from(Rgba::new(1, 2, 3, 4)), 0x01020304);
+    /// ```
+    #[inline]
+    fn from(value: Rgba) -> u32 {
+        u32::from_be_bytes([value.r, value.g, value.b, value.a])
+    }
+}
+
+impl From<Rgb> for Rgba {
+    /// Converts an opaque color, setting the alpha bits to `0xFF`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Rgb, Rgba};
+    ///
+    /// assert_eq!(Rgba::from(Rgb::new(1, 2, 3)), Rgba::new(1, 2, 3, 255));
+    /// ```
+    #[inline]
+    fn from(value: Rgb) -> Rgba {
+        Rgba::new(value.r, value.g, value.b, 0xFF)
+    }
+}
+
+impl From<Rgba> for Rgb {
+    /// Converts a color to an opaque color, as [`Rgba::rgb`]. The alpha bits are ignored.
+    #[inline]
+    fn from(value: Rgba) -> Rgb {
+        value.rgb()
+    }
+}
+
+impl fmt::Debug for Rgba {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Rgba")
+            .field(&self.r)
+            .field(&self.g)
+            .field(&self.b)
+            .field(&self.a)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn u32_round_trip() {
+        for value in (0..=u32::MAX).step_by(65_521).chain([0, 0xFF, 0xFFFF_FF00, u32::MAX]) {
+            let color = Rgba::from_u32(value);
+
+            assert_eq!(u32::from(color), value);
+            assert_eq!(Rgba::from(value), color);
+            assert_eq!(Rgb::from(color), Rgb::from(value));
+            assert_eq!(u32::from(Rgba::from(color.rgb())), u32::from(Rgb::from(value)));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt::{self, Formatter};
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ::serde::de::{Error, MapAccess, Visitor};
+use ::serde::ser::SerializeMap;
+
+use crate::{Dye, ParseDyeError, Rgb, Snack, SnackList};
+
+/// The short names of all the dyes, in the same order as [`Dye::VALUES`].
+static DYE_NAMES: [&str; Dye::COUNT] = {
+    let mut names = [""; Dye::COUNT];
+
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Dye::VALUES[i].short_name();
+        i += 1;
+    }
+
+    names
+};
+
+/// The short names of all the snacks, in the same order as [`Snack::VALUES`].
+static SNACK_NAMES: [&str; Snack::COUNT] = {
+    let mut names = [""; Snack::COUNT];
+
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Snack::VALUES[i].short_name();
+        i += 1;
+    }
+
+    names
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Rgb {
+    /// Serializes `self` as a lowercase hex color, e.g. `"#5bcefa"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Rgb {
+    /// Deserializes a hex color, as parsed by [`Rgb::from_hex`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rgb, D::Error> {
+        struct RgbVisitor;
+
+        impl Visitor<'_> for RgbVisitor {
+            type Value = Rgb;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a hex color")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Rgb, E> {
+                Rgb::from_hex(v).map_err(|e| E::custom(format_args!("invalid hex color `{v}`: {e}")))
+            }
+        }
+
+        deserializer.deserialize_str(RgbVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Dye {
+    /// Serializes `self` as its [short name](Dye::short_name), e.g. `"snow-white"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.short_name())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Dye {
+    /// Deserializes a dye from its [short name](Dye::short_name).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Dye, D::Error> {
+        struct DyeVisitor;
+
+        impl Visitor<'_> for DyeVisitor {
+            type Value = Dye;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("the kebab-case name of a dye")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Dye, E> {
+                v.parse().map_err(|ParseDyeError| E::unknown_variant(v, &DYE_NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(DyeVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Snack {
+    /// Serializes `self` as its [short name](Snack::short_name), e.g. `"apple"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.short_name())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Snack {
+    /// Deserializes a snack from its [short name](Snack::short_name).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Snack, D::Error> {
+        struct SnackVisitor;
+
+        impl Visitor<'_> for SnackVisitor {
+            type Value = Snack;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("the kebab-case name of a snack")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Snack, E> {
+                Snack::VALUES.into_iter().find(|snack| snack.short_name() == v).ok_or_else(|| E::unknown_variant(v, &SNACK_NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(SnackVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for SnackList {
+    /// Serializes `self` as a map from the snacks contained to their counts, e.g. `{"apple": 7, "pear": 3}`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(usize::from(self.kinds())))?;
+
+        for (snack, count) in self.into_iter().filter(|(_, count)| *count > 0) {
+            map.serialize_entry(&snack, &count)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for SnackList {
+    /// Deserializes a map from snacks to their counts; missing snacks have a count of zero.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SnackList, D::Error> {
+        struct SnackListVisitor;
+
+        impl<'de> Visitor<'de> for SnackListVisitor {
+            type Value = SnackList;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map from snacks to their counts")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<SnackList, A::Error> {
+                let mut snacks = SnackList::new();
+
+                while let Some((snack, count)) = map.next_entry::<Snack, u8>()? {
+                    snacks.set(snack, count);
+                }
+
+                Ok(snacks)
+            }
+        }
+
+        deserializer.deserialize_map(SnackListVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let color = Rgb::new(91, 206, 250);
+        assert_eq!(serde_json::to_string(&color).unwrap(), r##""#5bcefa""##);
+        assert_eq!(serde_json::from_str::<Rgb>(r##""#5bcefa""##).unwrap(), color);
+
+        for dye in Dye::VALUES {
+            let json = serde_json::to_string(&dye).unwrap();
+
+            assert_eq!(json, format!("\"{}\"", dye.short_name()));
+            assert_eq!(serde_json::from_str::<Dye>(&json).unwrap(), dye);
+        }
+
+        for snack in Snack::VALUES {
+            assert_eq!(serde_json::from_str::<Snack>(&serde_json::to_string(&snack).unwrap()).unwrap(), snack);
+        }
+
+        let snacks = SnackList::from([Snack::Apple, Snack::Plum, Snack::Apple].as_slice());
+        assert_eq!(serde_json::to_string(&snacks).unwrap(), r#"{"apple":2,"plum":1}"#);
+        assert_eq!(serde_json::from_str::<SnackList>(r#"{"apple":2,"plum":1}"#).unwrap(), snacks);
+        assert_eq!(serde_json::from_str::<SnackList>("{}").unwrap(), SnackList::new());
+    }
+
+    #[test]
+    fn unknown_names() {
+        let e = serde_json::from_str::<Dye>(r#""snow white""#).unwrap_err().to_string();
+        assert!(e.contains("unknown variant `snow white`"), "{e}");
+
+        let e = serde_json::from_str::<Snack>(r#""banana""#).unwrap_err().to_string();
+        assert!(e.contains("unknown variant `banana`, expected one of `apple`"), "{e}");
+
+        assert_eq!(serde_json::from_str::<SnackList>(r#"{"banana":1}"#).ok(), None);
+        assert_eq!(serde_json::from_str::<Rgb>(r##""#fffff""##).ok(), None);
+    }
+}
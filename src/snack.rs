@@ -5,6 +5,9 @@ use crate::Rgb;
 #[cfg(feature = "fluent")]
 use crate::{FluentBundle, message};
 
+#[cfg(all(feature = "fluent", feature = "truecolor"))]
+use crate::truecolor::{self, ColorSupport};
+
 /// A type of bitter fruit that changes the hue of the chocobos that eat it.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[repr(u8)]
@@ -29,8 +32,11 @@ pub enum Snack {
 }
 
 impl Snack {
+    /// The number of `Snack` variants, six.
+    pub const COUNT: usize = 6;
+
     /// Contains all six `Snack` variants.
-    pub const VALUES: [Snack; 6] = [
+    pub const VALUES: [Snack; Snack::COUNT] = [
         Snack::Apple,
         Snack::Pear,
         Snack::Berries,
@@ -39,6 +45,20 @@ impl Snack {
         Snack::Pineapple
     ];
 
+    /// Returns an iterator over all six `Snack` variants, in the same order as [`Snack::VALUES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Snack;
+    ///
+    /// assert_eq!(Snack::iter().count(), 6);
+    /// ```
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Snack> {
+        Snack::VALUES.into_iter()
+    }
+
     /// Returns the variant name of `self` in kebab-case.
     ///
     /// # Examples
@@ -61,6 +81,74 @@ impl Snack {
         }
     }
 
+    /// Returns an emoji representing `self`, for compact displays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Snack;
+    ///
+    /// assert_eq!(Snack::Pineapple.glyph(), '🍍');
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn glyph(self) -> char {
+        match self {
+            Snack::Apple     => '🍎',
+            Snack::Pear      => '🍐',
+            Snack::Berries   => '🫐',
+            Snack::Plum      => '🍇',
+            Snack::Fruit     => '🥭',
+            Snack::Pineapple => '🍍'
+        }
+    }
+
+    /// Returns the region where `self` is grown, as named in its description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Snack;
+    ///
+    /// assert_eq!(Snack::Apple.source_region(), "Xelphatol");
+    /// assert_eq!(Snack::Fruit.source_region(), "Isle of Val");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn source_region(self) -> &'static str {
+        match self {
+            Snack::Apple     => "Xelphatol",
+            Snack::Pear      => "Mamook",
+            Snack::Berries   => "O'Ghomoro",
+            Snack::Plum      => "Doma",
+            Snack::Fruit     => "Isle of Val",
+            Snack::Pineapple => "Cieldalaes"
+        }
+    }
+
+    /// Returns the color towards which `self` changes a chocobo's plumage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Rgb, Snack};
+    ///
+    /// assert_eq!(Snack::Apple.color(), Rgb::RED);
+    /// assert_eq!(Snack::Plum.color(), Rgb::CYAN);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn color(self) -> Rgb {
+        match self {
+            Snack::Apple     => Rgb::RED,
+            Snack::Pear      => Rgb::GREEN,
+            Snack::Berries   => Rgb::BLUE,
+            Snack::Plum      => Rgb::CYAN,
+            Snack::Fruit     => Rgb::MAGENTA,
+            Snack::Pineapple => Rgb::YELLOW
+        }
+    }
+
     /// Returns the localized quantified name of `self`.
     ///
     /// # Examples
@@ -76,6 +164,33 @@ impl Snack {
         message!(bundle, self.short_name(), { "quantity" = quantity })
     }
 
+    /// Returns the [glyph](Snack::glyph) of `self` followed by its localized quantified name,
+    /// with [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit) for display in `stdout`.
+    ///
+    /// For more documentation, check the [`ansi_text`](crate::ansi_text) function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::{Lang, Snack};
+    /// use std::env;
+    ///
+    /// env::remove_var("COLORTERM");
+    /// assert_eq!(Snack::Apple.display_entry(&Lang::English.into_bundle(), 3), "🍎 \u{2068}3\u{2069} Xelphatol Apples");
+    /// ```
+    #[cfg(all(feature = "fluent", feature = "truecolor"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "fluent", feature = "truecolor"))))]
+    #[must_use]
+    pub fn display_entry(self, bundle: &FluentBundle, count: u32) -> String {
+        self.display_entry_with(bundle, count, truecolor::terminal_color_support())
+    }
+
+    /// Like [`Snack::display_entry`], but with the colors supported by the terminal given explicitly.
+    #[cfg(all(feature = "fluent", feature = "truecolor"))]
+    fn display_entry_with(self, bundle: &FluentBundle, count: u32, support: ColorSupport) -> String {
+        format!("{} {}", self.glyph(), truecolor::ansi_text_with(support, self.color(), &self.quantified_name(bundle, count)))
+    }
+
     /// Returns the effect `self` will have on a chocobo's plumage.
     ///
     /// # Examples
@@ -98,6 +213,32 @@ impl Snack {
         }
     }
 
+    /// Returns the snack having the specified effect, or `None` if no snack has this effect.
+    ///
+    /// This is the inverse of [`Snack::effect`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Snack;
+    ///
+    /// assert_eq!(Snack::from_effect((-5, 5, 5)), Some(Snack::Plum));
+    /// assert_eq!(Snack::from_effect((5, 5, 5)), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_effect(effect: (i8, i8, i8)) -> Option<Snack> {
+        match effect {
+            ( 5, -5, -5) => Some(Snack::Apple),
+            (-5,  5, -5) => Some(Snack::Pear),
+            (-5, -5,  5) => Some(Snack::Berries),
+            (-5,  5,  5) => Some(Snack::Plum),
+            ( 5, -5,  5) => Some(Snack::Fruit),
+            ( 5,  5, -5) => Some(Snack::Pineapple),
+            _ => None
+        }
+    }
+
     /// Returns the color a chocobo would have if it ate this snack, or `None` if any color component had overflowed.
     ///
     /// # Examples
@@ -117,6 +258,26 @@ impl Snack {
     }
 }
 
+impl TryFrom<u8> for Snack {
+    /// The out-of-range discriminant.
+    type Error = u8;
+
+    /// Returns the snack whose discriminant is `value`, the inverse of `snack as u8`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chocodye::Snack;
+    ///
+    /// assert_eq!(Snack::try_from(Snack::Plum as u8), Ok(Snack::Plum));
+    /// assert_eq!(Snack::try_from(6), Err(6));
+    /// ```
+    #[inline]
+    fn try_from(value: u8) -> Result<Snack, u8> {
+        Snack::VALUES.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
 impl Neg for Snack {
     type Output = Snack;
 
@@ -147,6 +308,11 @@ impl Neg for Snack {
 mod test {
     use super::*;
 
+    #[test]
+    fn count() {
+        assert_eq!(Snack::COUNT, Snack::VALUES.len());
+    }
+
     #[test]
     fn neg() {
         for snack in Snack::VALUES {
@@ -158,4 +324,70 @@ mod test {
             assert_eq!(a.2 + b.2, 0);
         }
     }
+
+    #[test]
+    fn from_effect() {
+        for snack in Snack::VALUES {
+            assert_eq!(Snack::from_effect(snack.effect()), Some(snack));
+        }
+
+        assert_eq!(Snack::from_effect((0, 0, 0)), None);
+        assert_eq!(Snack::from_effect((-5, -5, -5)), None);
+        assert_eq!(Snack::from_effect((10, -10, -10)), None);
+    }
+
+    #[test]
+    fn try_from_u8() {
+        for snack in Snack::VALUES {
+            assert_eq!(Snack::try_from(snack as u8), Ok(snack));
+        }
+
+        assert_eq!(Snack::try_from(6), Err(6));
+        assert_eq!(Snack::try_from(u8::MAX), Err(u8::MAX));
+    }
+
+    #[test]
+    fn color_follows_effect() {
+        for snack in Snack::VALUES {
+            let (r, g, b) = snack.effect();
+            let color = snack.color();
+
+            assert_eq!((color.r > 0, color.g > 0, color.b > 0), (r > 0, g > 0, b > 0), "{snack:?}");
+        }
+    }
+
+    #[cfg(all(feature = "fluent", feature = "truecolor"))]
+    #[test]
+    fn display_entry() {
+        use crate::Lang;
+
+        for lang in Lang::VALUES {
+            let bundle = lang.into_bundle();
+
+            for snack in Snack::VALUES {
+                let entry = snack.display_entry_with(&bundle, 3, ColorSupport::None);
+
+                assert!(entry.starts_with(snack.glyph()), "{entry:?}");
+                assert!(entry.ends_with(&snack.quantified_name(&bundle, 3)), "{entry:?}");
+                assert!(!entry.contains('\x1B'), "{entry:?}");
+
+                let entry = snack.display_entry_with(&bundle, 3, ColorSupport::TrueColor);
+
+                assert!(entry.starts_with(&format!("{} \x1B[48;2;", snack.glyph())), "{entry:?}");
+                assert!(entry.ends_with(&format!("{}\x1B[0m", snack.quantified_name(&bundle, 3))), "{entry:?}");
+            }
+        }
+
+        let bundle = Lang::English.into_bundle();
+        assert_eq!(Snack::Apple.display_entry_with(&bundle, 3, ColorSupport::None), "🍎 \u{2068}3\u{2069} Xelphatol Apples");
+    }
+
+    #[test]
+    fn distinct_glyphs() {
+        for a in Snack::VALUES {
+            for b in Snack::VALUES {
+                assert_eq!(a == b, a.glyph() == b.glyph(), "{a:?} and {b:?} share the same glyph");
+            }
+        }
+    }
 }
@@ -4,53 +4,168 @@ use std::env;
 
 use crate::Rgb;
 
-fn is_supported() -> bool {
-    env::var("COLORTERM").ok().is_some_and(|s| s == "truecolor" || s == "24bit")
+/// The colors supported by the terminal.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ColorSupport {
+    /// No colors, the text is printed as is.
+    None,
+
+    /// The 16 standard ANSI colors.
+    Ansi16,
+
+    /// 24-bit colors.
+    TrueColor
+}
+
+/// Returns the colors supported by the terminal, according to the value of the `COLORTERM` environment variable.
+fn color_support(colorterm: Option<&str>) -> ColorSupport {
+    match colorterm {
+        Some("truecolor" | "24bit") => ColorSupport::TrueColor,
+        Some(s) if !s.is_empty() => ColorSupport::Ansi16,
+        _ => ColorSupport::None
+    }
+}
+
+/// Returns the colors supported by the terminal running this process.
+pub(crate) fn terminal_color_support() -> ColorSupport {
+    color_support(env::var("COLORTERM").ok().as_deref())
 }
 
 /// Changes the background color of a string using three [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit)
 /// if the terminal support [truecolors](https://en.wikipedia.org/wiki/Color_depth#True_color_(24-bit)).
 ///
-/// If the environment variable `COLORTERM` is not defined or contains neither `truecolor` nor `24bit`, the string parameter
-/// is returned as is.
+/// The colors supported by the terminal are detected with the environment variable `COLORTERM`:
+///
+/// - if it contains `truecolor` or `24bit`, the 24-bit escape codes are used;
+/// - otherwise, if it is defined and not empty, the terminal is assumed to support colors, and [`ansi_text_16`]
+///   is used as a fallback, with the closest of the 16 standard colors;
+/// - otherwise, the string parameter is returned as is.
 ///
-/// This function also changes the foreground color according to the specified background color in order to ensure that the
-/// text is visible.
+/// This function also changes the foreground color to the [most readable](Rgb::readable_text_color) one against the
+/// specified background color, in order to ensure that the text is visible.
 ///
 /// # Examples
 ///
 /// ```
-/// use chocodye::{Rgb, ansi_text};
+/// use chocodye::{Rgb, ansi_text, ansi_text_16};
 /// use std::env;
 ///
 /// env::remove_var("COLORTERM");
 /// assert_eq!(ansi_text(Rgb::RED, "hello world!"), "hello world!");
 ///
 /// env::set_var("COLORTERM", "truecolor");
-/// assert_eq!(ansi_text(Rgb::RED, "hello world!"), "\x1B[48;2;255;0;0m\x1B[38;2;255;255;255mhello world!\x1B[0m");
-/// //                                                         ^^^^^^^           ^^^^^^^^^^^ ^^^^^^^^^^^^
-/// //                                                        background          foreground     text
+/// assert_eq!(ansi_text(Rgb::BLUE, "hello world!"), "\x1B[48;2;0;0;255m\x1B[38;2;255;255;255mhello world!\x1B[0m");
+/// //                                                          ^^^^^^^           ^^^^^^^^^^^ ^^^^^^^^^^^^
+/// //                                                         background          foreground     text
+///
+/// env::set_var("COLORTERM", "1");
+/// assert_eq!(ansi_text(Rgb::BLUE, "hello world!"), ansi_text_16(Rgb::BLUE, "hello world!"));
 /// ```
 #[cfg_attr(docsrs, doc(cfg(feature = "truecolor")))]
 #[must_use]
 pub fn ansi_text(bg: Rgb, s: &str) -> String {
-    if !is_supported() {
-        s.to_owned()
+    ansi_text_with(terminal_color_support(), bg, s)
+}
+
+/// Like [`ansi_text`], but with the colors supported by the terminal given explicitly.
+pub(crate) fn ansi_text_with(support: ColorSupport, bg: Rgb, s: &str) -> String {
+    match support {
+        ColorSupport::None => s.to_owned(),
+        ColorSupport::Ansi16 => ansi_text_16(bg, s),
+        ColorSupport::TrueColor => {
+            let fg = bg.readable_text_color();
+
+            format!("\x1B[48;2;{};{};{}m\x1B[38;2;{};{};{}m{s}\x1B[0m",
+               bg.r, bg.g, bg.b,
+               fg.r, fg.g, fg.b
+            )
+        }
+    }
+}
+
+/// The 16 standard ANSI colors, as rendered by xterm.
+const ANSI_16: [Rgb; 16] = [
+    Rgb::new(0, 0, 0),
+    Rgb::new(205, 0, 0),
+    Rgb::new(0, 205, 0),
+    Rgb::new(205, 205, 0),
+    Rgb::new(0, 0, 238),
+    Rgb::new(205, 0, 205),
+    Rgb::new(0, 205, 205),
+    Rgb::new(229, 229, 229),
+    Rgb::new(127, 127, 127),
+    Rgb::new(255, 0, 0),
+    Rgb::new(0, 255, 0),
+    Rgb::new(255, 255, 0),
+    Rgb::new(92, 92, 255),
+    Rgb::new(255, 0, 255),
+    Rgb::new(0, 255, 255),
+    Rgb::new(255, 255, 255)
+];
+
+/// Returns the index of the closest standard ANSI color to `color`, between `0` and `15`.
+fn ansi_16_index(color: Rgb) -> u8 {
+    (0..16).min_by_key(|&i| ANSI_16[usize::from(i)].distance(color)).unwrap_or_default()
+}
+
+/// Changes the background color of a string using the closest of the 16 standard
+/// [ANSI colors](https://en.wikipedia.org/wiki/ANSI_escape_code#3-bit_and_4-bit).
+///
+/// This is the fallback used by [`ansi_text`] for terminals not supporting truecolors; unlike `ansi_text`,
+/// the escape codes are always emitted.
+///
+/// `bg` is mapped to the nearest color of the xterm palette: the eight normal colors (black, red, green, yellow, blue,
+/// magenta, cyan and white) are emitted as `\x1B[40m` through `\x1B[47m`, and their bright variants as `\x1B[100m` through
+/// `\x1B[107m`. The foreground is then set to either black (`\x1B[30m`) or bright white (`\x1B[97m`), whichever is the
+/// [most readable](Rgb::readable_text_color).
+///
+/// # Examples
+///
+/// ```
+/// use chocodye::{Rgb, ansi_text_16};
+///
+/// assert_eq!(ansi_text_16(Rgb::RED, "hello world!"), "\x1B[101m\x1B[30mhello world!\x1B[0m");
+/// assert_eq!(ansi_text_16(Rgb::new(0, 30, 10), "hello world!"), "\x1B[40m\x1B[97mhello world!\x1B[0m");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "truecolor")))]
+#[must_use]
+pub fn ansi_text_16(bg: Rgb, s: &str) -> String {
+    let n = ansi_16_index(bg);
+
+    let fg = if ANSI_16[usize::from(n)].readable_text_color() == Rgb::WHITE { 97 } else { 30 };
+
+    if n < 8 {
+        format!("\x1B[4{n}m\x1B[{fg}m{s}\x1B[0m")
     }
     else {
-        let fg = {
-            const LIMIT: u32 = Rgb::gray(127).distance(Rgb::WHITE);
-            
-            if bg.distance(Rgb::WHITE) >= LIMIT {
-                Rgb::WHITE
-            } else {
-                Rgb::BLACK
-            }
-        };
-
-        format!("\x1B[48;2;{};{};{}m\x1B[38;2;{};{};{}m{s}\x1B[0m",
-           bg.r, bg.g, bg.b,
-           fg.r, fg.g, fg.b
-        )
+        format!("\x1B[10{}m\x1B[{fg}m{s}\x1B[0m", n - 8)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn red_is_ansi_red() {
+        assert!(matches!(ansi_16_index(Rgb::RED), 1 | 9));
+        assert!(ansi_text_16(Rgb::RED, "").starts_with("\x1B[101m"));
+    }
+
+    #[test]
+    fn palette_maps_to_itself() {
+        for (i, color) in (0..).zip(ANSI_16) {
+            assert_eq!(ansi_16_index(color), i);
+        }
+    }
+
+    #[test]
+    fn color_support_chain() {
+        assert_eq!(color_support(Some("truecolor")), ColorSupport::TrueColor);
+        assert_eq!(color_support(Some("24bit")), ColorSupport::TrueColor);
+        assert_eq!(color_support(Some("1")), ColorSupport::Ansi16);
+        assert_eq!(color_support(Some("rxvt-xpm")), ColorSupport::Ansi16);
+        assert_eq!(color_support(Some("")), ColorSupport::None);
+        assert_eq!(color_support(None), ColorSupport::None);
     }
 }